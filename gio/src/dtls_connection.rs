@@ -0,0 +1,506 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{
+    cell::Cell,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll},
+    time::Duration,
+};
+#[cfg(feature = "v2_60")]
+use std::{cell::RefCell, ptr};
+
+use futures_channel::{mpsc, oneshot};
+use futures_core::stream::Stream;
+use futures_util::future::{self, Either};
+#[cfg(feature = "v2_60")]
+use glib::translate::*;
+use glib::{prelude::*, SignalHandlerId, WeakRef};
+
+use crate::{
+    prelude::*, Cancellable, DtlsConnection, IOErrorEnum, TlsCertificate, TlsCertificateFlags,
+    TlsError,
+};
+
+pub trait DtlsConnectionExtManual: IsA<DtlsConnection> {
+    // rustdoc-stripper-ignore-next
+    /// Returns a [`Stream`] of peer certificates that need to be validated.
+    ///
+    /// Each item carries the certificate, the errors found by the default validation and a
+    /// sender through which the certificate is accepted (`true`) or rejected (`false`). This
+    /// allows validating certificates asynchronously.
+    ///
+    /// `accept-certificate` is a synchronous signal, so while waiting for the answer a nested
+    /// main loop is run on the thread-default main context. The wait is bounded by `timeout`:
+    /// if no answer arrives in time, or the sender is dropped, the certificate is rejected.
+    ///
+    /// The signal is emitted on the thread that runs the handshake. That thread must be able to
+    /// acquire its thread-default main context, i.e. the context must not be owned by another
+    /// thread. Otherwise the nested main loop can't be run and every certificate is rejected,
+    /// which is logged as a critical warning.
+    ///
+    /// The signal handler is disconnected when the stream is dropped.
+    #[doc(alias = "accept-certificate")]
+    fn accept_certificate_stream(
+        &self,
+        timeout: Duration,
+    ) -> Pin<
+        Box<
+            dyn Stream<Item = (TlsCertificate, TlsCertificateFlags, oneshot::Sender<bool>)>
+                + 'static,
+        >,
+    > {
+        let (sender, receiver) = mpsc::unbounded();
+
+        let connection = self.upcast_ref::<DtlsConnection>();
+        let handler = connection.connect_accept_certificate(move |_, cert, errors| {
+            let (answer_sender, answer_receiver) = oneshot::channel();
+            if sender
+                .unbounded_send((cert.clone(), errors, answer_sender))
+                .is_err()
+            {
+                return false;
+            }
+
+            wait_for_answer(answer_receiver, timeout)
+        });
+
+        Box::pin(AcceptCertificateStream {
+            receiver,
+            connection: connection.downgrade(),
+            handler: Some(handler),
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Like [`handshake_future()`](DtlsConnectionExt::handshake_future) but fails with
+    /// [`IOErrorEnum::TimedOut`] if the handshake does not complete within `timeout`.
+    ///
+    /// On timeout the in-flight handshake is cancelled.
+    fn handshake_future_with_timeout(
+        &self,
+        io_priority: glib::Priority,
+        timeout: Duration,
+    ) -> Pin<Box<dyn Future<Output = Result<(), glib::Error>> + 'static>> {
+        // Dropping the handshake future cancels the underlying operation
+        let handshake = self.handshake_future(io_priority);
+        Box::pin(async move {
+            glib::future_with_timeout(timeout, handshake)
+                .await
+                .unwrap_or_else(|_| {
+                    Err(glib::Error::new(
+                        IOErrorEnum::TimedOut,
+                        "DTLS handshake timed out",
+                    ))
+                })
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Performs another handshake on an already established connection.
+    ///
+    /// This replaces the deprecated rehandshake mode by calling
+    /// [`handshake()`](DtlsConnectionExt::handshake) again, which triggers a rehandshake up to
+    /// GLib 2.62.
+    ///
+    /// Since GLib 2.64 calling `handshake()` after the initial handshake does nothing, as TLS 1.3
+    /// removed renegotiation from the protocol. This therefore fails with
+    /// [`IOErrorEnum::NotSupported`] if the GLib version at runtime is 2.64 or newer. Key
+    /// rotation then has to be done by setting up a new connection.
+    #[doc(alias = "g_dtls_connection_handshake")]
+    fn rehandshake(&self, cancellable: Option<&impl IsA<Cancellable>>) -> Result<(), glib::Error> {
+        if glib::check_version(2, 64, 0).is_none() {
+            return Err(glib::Error::new(
+                IOErrorEnum::NotSupported,
+                "Rehandshaking is not supported since GLib 2.64",
+            ));
+        }
+
+        self.handshake(cancellable)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Like [`close_future()`](DtlsConnectionExt::close_future) but fails with
+    /// [`IOErrorEnum::TimedOut`] if the close does not complete within `timeout`.
+    ///
+    /// This bounds the wait for a peer that never acknowledges the close notification. On
+    /// timeout the in-flight close is cancelled.
+    fn close_future_with_timeout(
+        &self,
+        io_priority: glib::Priority,
+        timeout: Duration,
+    ) -> Pin<Box<dyn Future<Output = Result<(), glib::Error>> + 'static>> {
+        // Dropping the close future cancels the underlying operation
+        let close = self.close_future(io_priority);
+        Box::pin(async move {
+            glib::future_with_timeout(timeout, close)
+                .await
+                .unwrap_or_else(|_| {
+                    Err(glib::Error::new(
+                        IOErrorEnum::TimedOut,
+                        "DTLS close timed out",
+                    ))
+                })
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Shuts down only the read side of the connection.
+    ///
+    /// This is [`shutdown_future()`](DtlsConnectionExt::shutdown_future) with only
+    /// `shutdown_read` set.
+    #[doc(alias = "g_dtls_connection_shutdown_async")]
+    fn shutdown_read_future(
+        &self,
+        io_priority: glib::Priority,
+    ) -> Pin<Box<dyn Future<Output = Result<(), glib::Error>> + 'static>> {
+        self.shutdown_future(true, false, io_priority)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Shuts down only the write side of the connection.
+    ///
+    /// This is [`shutdown_future()`](DtlsConnectionExt::shutdown_future) with only
+    /// `shutdown_write` set.
+    #[doc(alias = "g_dtls_connection_shutdown_async")]
+    fn shutdown_write_future(
+        &self,
+        io_priority: glib::Priority,
+    ) -> Pin<Box<dyn Future<Output = Result<(), glib::Error>> + 'static>> {
+        self.shutdown_future(false, true, io_priority)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets or clears the certificate presented to the peer.
+    ///
+    /// `g_dtls_connection_set_certificate()` doesn't accept `NULL`, so this sets the
+    /// `certificate` property directly, which allows passing `None` to clear a previously set
+    /// certificate.
+    #[doc(alias = "g_dtls_connection_set_certificate")]
+    #[doc(alias = "certificate")]
+    fn set_certificate_opt(&self, certificate: Option<&impl IsA<TlsCertificate>>) {
+        self.set_property("certificate", certificate.map(|c| c.as_ref()));
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the certificate chain presented by the peer.
+    ///
+    /// The chain starts with [`peer_certificate()`](DtlsConnectionExt::peer_certificate) and
+    /// follows [`issuer()`](crate::prelude::TlsCertificateExt::issuer) from there, so the root
+    /// is last. It is empty if there is no peer certificate yet. Should the chain loop back
+    /// to a certificate that was already seen, it ends there.
+    #[doc(alias = "peer-certificate")]
+    fn peer_certificate_chain(&self) -> Vec<TlsCertificate> {
+        let mut chain = Vec::<TlsCertificate>::new();
+        let mut next = self.peer_certificate();
+        while let Some(cert) = next {
+            if chain.iter().any(|c| c.is_same(&cert)) {
+                break;
+            }
+            next = cert.issuer();
+            chain.push(cert);
+        }
+
+        chain
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns `true` once the handshake has completed successfully.
+    ///
+    /// [`negotiated_protocol()`](DtlsConnectionExt::negotiated_protocol) returns `None` both
+    /// before the handshake and when no application-layer protocol was agreed on. This allows
+    /// telling the two cases apart.
+    ///
+    /// This relies on [`protocol_version()`](DtlsConnectionExt::protocol_version), which is
+    /// only available since GLib 2.70 and reports
+    /// [`TlsProtocolVersion::Unknown`](crate::TlsProtocolVersion::Unknown) until the
+    /// handshake is done.
+    #[cfg(feature = "v2_70")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "v2_70")))]
+    fn has_negotiated(&self) -> bool {
+        self.protocol_version() != crate::TlsProtocolVersion::Unknown
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a future that resolves with the negotiated application-layer protocol.
+    ///
+    /// If a protocol was already negotiated the future resolves immediately, otherwise it
+    /// resolves once `negotiated-protocol` becomes non-`NULL`, usually during the handshake.
+    ///
+    /// If the handshake completes without agreeing on a protocol, e.g. because the peer doesn't
+    /// support ALPN, the future resolves with `None`. Completion of the handshake is detected
+    /// through the change notification of `protocol-version`, which only exists since GLib 2.70.
+    /// With older GLib versions the future would stay pending until the connection is destroyed,
+    /// so race it with the handshake there, e.g. with `futures::future::select()`. The future
+    /// also resolves with `None` if the connection is destroyed.
+    #[cfg(feature = "v2_60")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "v2_60")))]
+    #[doc(alias = "negotiated-protocol")]
+    fn negotiated_protocol_future(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Option<glib::GString>> + 'static>> {
+        if let Some(protocol) = self.negotiated_protocol() {
+            return Box::pin(future::ready(Some(protocol)));
+        }
+
+        let (sender, receiver) = oneshot::channel();
+        let sender = Rc::new(RefCell::new(Some(sender)));
+
+        let connection = self.upcast_ref::<DtlsConnection>();
+        let protocol_handler = connection.connect_negotiated_protocol_notify(glib::clone!(
+            #[strong]
+            sender,
+            move |connection| {
+                if let Some(protocol) = connection.negotiated_protocol() {
+                    if let Some(sender) = sender.borrow_mut().take() {
+                        let _ = sender.send(Some(protocol));
+                    }
+                }
+            }
+        ));
+        // The protocol version is only known once the handshake is done. Connect by name, as
+        // the property is only available since GLib 2.70.
+        let handshake_handler =
+            connection.connect_notify_local(Some("protocol-version"), move |connection, _| {
+                if let Some(sender) = sender.borrow_mut().take() {
+                    let _ = sender.send(connection.negotiated_protocol());
+                }
+            });
+
+        Box::pin(NegotiatedProtocolFuture {
+            receiver,
+            connection: connection.downgrade(),
+            handlers: vec![protocol_handler, handshake_handler],
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Clears the list of application-layer protocols advertised during the handshake.
+    ///
+    /// This passes `NULL` to the C function, which disables ALPN negotiation entirely. This is
+    /// not the same as setting an empty list with
+    /// [`set_advertised_protocols()`](DtlsConnectionExt::set_advertised_protocols): ALPN then
+    /// stays enabled but no protocols are offered, and how that is handled is up to the TLS
+    /// backend.
+    #[cfg(feature = "v2_60")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "v2_60")))]
+    #[doc(alias = "g_dtls_connection_set_advertised_protocols")]
+    fn clear_advertised_protocols(&self) {
+        unsafe {
+            crate::ffi::g_dtls_connection_set_advertised_protocols(
+                self.as_ref().to_glib_none().0,
+                ptr::null(),
+            );
+        }
+    }
+}
+
+impl<O: IsA<DtlsConnection>> DtlsConnectionExtManual for O {}
+
+// rustdoc-stripper-ignore-next
+/// Whether a DTLS connection was closed cleanly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DtlsCloseStatus {
+    // rustdoc-stripper-ignore-next
+    /// The connection was closed with a close notification.
+    Clean,
+    // rustdoc-stripper-ignore-next
+    /// The connection ended without a close notification.
+    ///
+    /// An attacker can use this to cut off data, so the data received so far may be
+    /// incomplete.
+    Truncated,
+}
+
+impl DtlsCloseStatus {
+    // rustdoc-stripper-ignore-next
+    /// Classifies the result of closing or shutting down a connection.
+    ///
+    /// [`TlsError::Eof`] and [`IOErrorEnum::BrokenPipe`] (also known as
+    /// `G_IO_ERROR_CONNECTION_CLOSED`), which are returned if the peer went away without a
+    /// close notification, are reported as [`Self::Truncated`]. All other errors are passed
+    /// through.
+    ///
+    /// Note that truncation is only reported as an error if
+    /// [`requires_close_notify()`](DtlsConnectionExt::requires_close_notify) is set.
+    pub fn from_result(result: Result<(), glib::Error>) -> Result<Self, glib::Error> {
+        match result {
+            Ok(()) => Ok(Self::Clean),
+            Err(err) if err.matches(TlsError::Eof) || err.matches(IOErrorEnum::BrokenPipe) => {
+                Ok(Self::Truncated)
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+fn wait_for_answer(answer: oneshot::Receiver<bool>, timeout: Duration) -> bool {
+    let main_context = glib::MainContext::ref_thread_default();
+    let Ok(_acquire) = main_context.acquire() else {
+        glib::g_critical!(
+            "GLib-GIO",
+            "Rejecting peer certificate: the thread-default main context is owned by another thread"
+        );
+        return false;
+    };
+
+    let main_loop = glib::MainLoop::new(Some(&main_context), false);
+    let accepted = Rc::new(Cell::new(false));
+    main_context.spawn_local(glib::clone!(
+        #[strong]
+        main_loop,
+        #[strong]
+        accepted,
+        async move {
+            if let Either::Left((Ok(answer), _)) =
+                future::select(answer, glib::timeout_future(timeout)).await
+            {
+                accepted.set(answer);
+            }
+            main_loop.quit();
+        }
+    ));
+    main_loop.run();
+
+    accepted.get()
+}
+
+struct AcceptCertificateStream {
+    receiver: mpsc::UnboundedReceiver<(TlsCertificate, TlsCertificateFlags, oneshot::Sender<bool>)>,
+    connection: WeakRef<DtlsConnection>,
+    handler: Option<SignalHandlerId>,
+}
+
+impl Stream for AcceptCertificateStream {
+    type Item = (TlsCertificate, TlsCertificateFlags, oneshot::Sender<bool>);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+impl Drop for AcceptCertificateStream {
+    fn drop(&mut self) {
+        if let (Some(connection), Some(handler)) = (self.connection.upgrade(), self.handler.take())
+        {
+            connection.disconnect(handler);
+        }
+    }
+}
+
+#[cfg(feature = "v2_60")]
+struct NegotiatedProtocolFuture {
+    receiver: oneshot::Receiver<Option<glib::GString>>,
+    connection: WeakRef<DtlsConnection>,
+    handlers: Vec<SignalHandlerId>,
+}
+
+#[cfg(feature = "v2_60")]
+impl NegotiatedProtocolFuture {
+    fn disconnect(&mut self) {
+        let handlers = std::mem::take(&mut self.handlers);
+        if let Some(connection) = self.connection.upgrade() {
+            for handler in handlers {
+                connection.disconnect(handler);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "v2_60")]
+impl Future for NegotiatedProtocolFuture {
+    type Output = Option<glib::GString>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match Pin::new(&mut self.receiver).poll(cx) {
+            Poll::Ready(res) => {
+                self.disconnect();
+                Poll::Ready(res.ok().flatten())
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(feature = "v2_60")]
+impl Drop for NegotiatedProtocolFuture {
+    fn drop(&mut self) {
+        self.disconnect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wait_on_new_context(answer: oneshot::Receiver<bool>, timeout: Duration) -> bool {
+        glib::MainContext::new()
+            .with_thread_default(|| wait_for_answer(answer, timeout))
+            .unwrap()
+    }
+
+    #[test]
+    fn accept_certificate_answer() {
+        let (sender, receiver) = oneshot::channel();
+        sender.send(true).unwrap();
+        assert!(wait_on_new_context(receiver, Duration::from_secs(10)));
+
+        let (sender, receiver) = oneshot::channel();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(10));
+            sender.send(true).unwrap();
+        });
+        assert!(wait_on_new_context(receiver, Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn accept_certificate_reject() {
+        let (sender, receiver) = oneshot::channel();
+        sender.send(false).unwrap();
+        assert!(!wait_on_new_context(receiver, Duration::from_secs(10)));
+
+        let (sender, receiver) = oneshot::channel::<bool>();
+        drop(sender);
+        assert!(!wait_on_new_context(receiver, Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn accept_certificate_timeout() {
+        let (_sender, receiver) = oneshot::channel();
+        assert!(!wait_on_new_context(receiver, Duration::from_millis(10)));
+    }
+
+    #[test]
+    fn close_status() {
+        assert_eq!(
+            DtlsCloseStatus::from_result(Ok(())).unwrap(),
+            DtlsCloseStatus::Clean
+        );
+        assert_eq!(
+            DtlsCloseStatus::from_result(Err(glib::Error::new(TlsError::Eof, "eof"))).unwrap(),
+            DtlsCloseStatus::Truncated
+        );
+        assert_eq!(
+            DtlsCloseStatus::from_result(Err(glib::Error::new(IOErrorEnum::BrokenPipe, "closed")))
+                .unwrap(),
+            DtlsCloseStatus::Truncated
+        );
+        let err =
+            DtlsCloseStatus::from_result(Err(glib::Error::new(IOErrorEnum::TimedOut, "timed out")))
+                .unwrap_err();
+        assert!(err.matches(IOErrorEnum::TimedOut));
+    }
+
+    #[cfg(feature = "v2_70")]
+    #[test]
+    fn protocol_version_ord() {
+        use crate::TlsProtocolVersion;
+
+        assert!(TlsProtocolVersion::Unknown < TlsProtocolVersion::Ssl30);
+        assert!(TlsProtocolVersion::Tls13 >= TlsProtocolVersion::Tls12);
+        assert!(TlsProtocolVersion::Tls11 < TlsProtocolVersion::Tls12);
+        assert!(TlsProtocolVersion::Dtls12 > TlsProtocolVersion::Dtls10);
+        assert!(TlsProtocolVersion::Dtls10 > TlsProtocolVersion::Unknown);
+    }
+}
@@ -41,6 +41,8 @@ mod dbus_node_info;
 mod debug_controller_dbus;
 #[cfg(all(not(windows), not(target_os = "macos")))]
 mod desktop_app_info;
+mod dtls_connection;
+pub use dtls_connection::DtlsCloseStatus;
 mod error;
 mod file;
 mod file_attribute_info;
@@ -104,6 +106,8 @@ mod unix_mount_point;
 mod unix_output_stream;
 #[cfg(unix)]
 mod unix_socket_address;
+mod volume_monitor;
+pub use volume_monitor::{DriveEntry, VolumeEntry, VolumeSnapshot};
 
 #[cfg(test)]
 mod test_util;
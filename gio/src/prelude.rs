@@ -35,7 +35,8 @@ pub use crate::{
     action_map::ActionMapExtManual, application::ApplicationExtManual, auto::traits::*,
     cancellable::CancellableExtManual, converter::ConverterExtManual,
     data_input_stream::DataInputStreamExtManual, datagram_based::DatagramBasedExtManual,
-    dbus_connection::DBusMethodCall, dbus_proxy::DBusProxyExtManual, file::FileExtManual,
+    dbus_connection::DBusMethodCall, dbus_proxy::DBusProxyExtManual,
+    dtls_connection::DtlsConnectionExtManual, file::FileExtManual,
     file_enumerator::FileEnumeratorExtManual, inet_address::InetAddressExtManual,
     input_stream::InputStreamExtManual, io_stream::IOStreamExtManual,
     list_model::ListModelExtManual, output_stream::OutputStreamExtManual,
@@ -44,4 +45,5 @@ pub use crate::{
     simple_proxy_resolver::SimpleProxyResolverExtManual, socket::SocketExtManual,
     socket_control_message::SocketControlMessageExtManual,
     socket_listener::SocketListenerExtManual, tls_connection::TlsConnectionExtManual,
+    volume_monitor::VolumeMonitorExtManual,
 };
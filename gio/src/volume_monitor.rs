@@ -0,0 +1,343 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{
+    cell::RefCell,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_channel::oneshot;
+use glib::{prelude::*, SignalHandlerId};
+
+use crate::{prelude::*, Drive, Mount, Volume, VolumeMonitor};
+
+pub trait VolumeMonitorExtManual: IsA<VolumeMonitor> {
+    // rustdoc-stripper-ignore-next
+    /// Returns a future that resolves with the next mount added to the monitor.
+    ///
+    /// The signal handler is disconnected once the future resolves or is dropped. The future
+    /// resolves with `None` if the handler is disconnected before the signal is emitted.
+    #[doc(alias = "mount-added")]
+    fn next_mount_added(&self) -> Pin<Box<dyn Future<Output = Option<Mount>> + 'static>> {
+        next_emission(self.upcast_ref::<VolumeMonitor>(), |monitor, f| {
+            monitor.connect_mount_added(move |_, mount| f(mount.clone()))
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a future that resolves with the next mount removed from the monitor.
+    ///
+    /// The signal handler is disconnected once the future resolves or is dropped. The future
+    /// resolves with `None` if the handler is disconnected before the signal is emitted.
+    #[doc(alias = "mount-removed")]
+    fn next_mount_removed(&self) -> Pin<Box<dyn Future<Output = Option<Mount>> + 'static>> {
+        next_emission(self.upcast_ref::<VolumeMonitor>(), |monitor, f| {
+            monitor.connect_mount_removed(move |_, mount| f(mount.clone()))
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a future that resolves with the next mount that changed.
+    ///
+    /// The signal handler is disconnected once the future resolves or is dropped. The future
+    /// resolves with `None` if the handler is disconnected before the signal is emitted.
+    #[doc(alias = "mount-changed")]
+    fn next_mount_changed(&self) -> Pin<Box<dyn Future<Output = Option<Mount>> + 'static>> {
+        next_emission(self.upcast_ref::<VolumeMonitor>(), |monitor, f| {
+            monitor.connect_mount_changed(move |_, mount| f(mount.clone()))
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a future that resolves with the next mount that is about to be unmounted.
+    ///
+    /// The signal handler is disconnected once the future resolves or is dropped. The future
+    /// resolves with `None` if the handler is disconnected before the signal is emitted.
+    #[doc(alias = "mount-pre-unmount")]
+    fn next_mount_pre_unmount(&self) -> Pin<Box<dyn Future<Output = Option<Mount>> + 'static>> {
+        next_emission(self.upcast_ref::<VolumeMonitor>(), |monitor, f| {
+            monitor.connect_mount_pre_unmount(move |_, mount| f(mount.clone()))
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a future that resolves with the next volume added to the monitor.
+    ///
+    /// The signal handler is disconnected once the future resolves or is dropped. The future
+    /// resolves with `None` if the handler is disconnected before the signal is emitted.
+    #[doc(alias = "volume-added")]
+    fn next_volume_added(&self) -> Pin<Box<dyn Future<Output = Option<Volume>> + 'static>> {
+        next_emission(self.upcast_ref::<VolumeMonitor>(), |monitor, f| {
+            monitor.connect_volume_added(move |_, volume| f(volume.clone()))
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a future that resolves with the next volume removed from the monitor.
+    ///
+    /// The signal handler is disconnected once the future resolves or is dropped. The future
+    /// resolves with `None` if the handler is disconnected before the signal is emitted.
+    #[doc(alias = "volume-removed")]
+    fn next_volume_removed(&self) -> Pin<Box<dyn Future<Output = Option<Volume>> + 'static>> {
+        next_emission(self.upcast_ref::<VolumeMonitor>(), |monitor, f| {
+            monitor.connect_volume_removed(move |_, volume| f(volume.clone()))
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a future that resolves with the next volume that changed.
+    ///
+    /// The signal handler is disconnected once the future resolves or is dropped. The future
+    /// resolves with `None` if the handler is disconnected before the signal is emitted.
+    #[doc(alias = "volume-changed")]
+    fn next_volume_changed(&self) -> Pin<Box<dyn Future<Output = Option<Volume>> + 'static>> {
+        next_emission(self.upcast_ref::<VolumeMonitor>(), |monitor, f| {
+            monitor.connect_volume_changed(move |_, volume| f(volume.clone()))
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a future that resolves with the next drive connected to the system.
+    ///
+    /// The signal handler is disconnected once the future resolves or is dropped. The future
+    /// resolves with `None` if the handler is disconnected before the signal is emitted.
+    #[doc(alias = "drive-connected")]
+    fn next_drive_connected(&self) -> Pin<Box<dyn Future<Output = Option<Drive>> + 'static>> {
+        next_emission(self.upcast_ref::<VolumeMonitor>(), |monitor, f| {
+            monitor.connect_drive_connected(move |_, drive| f(drive.clone()))
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a future that resolves with the next drive disconnected from the system.
+    ///
+    /// The signal handler is disconnected once the future resolves or is dropped. The future
+    /// resolves with `None` if the handler is disconnected before the signal is emitted.
+    #[doc(alias = "drive-disconnected")]
+    fn next_drive_disconnected(&self) -> Pin<Box<dyn Future<Output = Option<Drive>> + 'static>> {
+        next_emission(self.upcast_ref::<VolumeMonitor>(), |monitor, f| {
+            monitor.connect_drive_disconnected(move |_, drive| f(drive.clone()))
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a future that resolves with the next drive that changed.
+    ///
+    /// The signal handler is disconnected once the future resolves or is dropped. The future
+    /// resolves with `None` if the handler is disconnected before the signal is emitted.
+    #[doc(alias = "drive-changed")]
+    fn next_drive_changed(&self) -> Pin<Box<dyn Future<Output = Option<Drive>> + 'static>> {
+        next_emission(self.upcast_ref::<VolumeMonitor>(), |monitor, f| {
+            monitor.connect_drive_changed(move |_, drive| f(drive.clone()))
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a future that resolves with the next drive whose eject button was pressed.
+    ///
+    /// The signal handler is disconnected once the future resolves or is dropped. The future
+    /// resolves with `None` if the handler is disconnected before the signal is emitted.
+    #[doc(alias = "drive-eject-button")]
+    fn next_drive_eject_button(&self) -> Pin<Box<dyn Future<Output = Option<Drive>> + 'static>> {
+        next_emission(self.upcast_ref::<VolumeMonitor>(), |monitor, f| {
+            monitor.connect_drive_eject_button(move |_, drive| f(drive.clone()))
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a future that resolves with the next drive whose stop button was pressed.
+    ///
+    /// The signal handler is disconnected once the future resolves or is dropped. The future
+    /// resolves with `None` if the handler is disconnected before the signal is emitted.
+    #[doc(alias = "drive-stop-button")]
+    fn next_drive_stop_button(&self) -> Pin<Box<dyn Future<Output = Option<Drive>> + 'static>> {
+        next_emission(self.upcast_ref::<VolumeMonitor>(), |monitor, f| {
+            monitor.connect_drive_stop_button(move |_, drive| f(drive.clone()))
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a snapshot of the connected drives with their volumes and mounts.
+    ///
+    /// Volumes that don't belong to a drive and mounts that don't belong to a volume, e.g.
+    /// network mounts, are listed separately.
+    fn snapshot(&self) -> VolumeSnapshot {
+        let monitor = self.upcast_ref::<VolumeMonitor>();
+
+        let drives = monitor
+            .connected_drives()
+            .into_iter()
+            .map(|drive| DriveEntry {
+                volumes: drive.volumes().into_iter().map(VolumeEntry::new).collect(),
+                drive,
+            })
+            .collect();
+        let volumes_without_drive = monitor
+            .volumes()
+            .into_iter()
+            .filter(|volume| volume.drive().is_none())
+            .map(VolumeEntry::new)
+            .collect();
+        let mounts_without_volume = monitor
+            .mounts()
+            .into_iter()
+            .filter(|mount| mount.volume().is_none())
+            .collect();
+
+        VolumeSnapshot {
+            drives,
+            volumes_without_drive,
+            mounts_without_volume,
+        }
+    }
+}
+
+impl<O: IsA<VolumeMonitor>> VolumeMonitorExtManual for O {}
+
+// rustdoc-stripper-ignore-next
+/// A snapshot of the drives, volumes and mounts known to a [`VolumeMonitor`].
+///
+/// See [`VolumeMonitorExtManual::snapshot()`].
+#[derive(Debug, Clone)]
+pub struct VolumeSnapshot {
+    // rustdoc-stripper-ignore-next
+    /// The connected drives.
+    pub drives: Vec<DriveEntry>,
+    // rustdoc-stripper-ignore-next
+    /// Volumes that are not associated with a drive.
+    pub volumes_without_drive: Vec<VolumeEntry>,
+    // rustdoc-stripper-ignore-next
+    /// Mounts that are not associated with a volume, e.g. network mounts.
+    ///
+    /// All other mounts are found in the [`VolumeEntry`] of their volume.
+    pub mounts_without_volume: Vec<Mount>,
+}
+
+// rustdoc-stripper-ignore-next
+/// A drive together with its volumes.
+#[derive(Debug, Clone)]
+pub struct DriveEntry {
+    // rustdoc-stripper-ignore-next
+    /// The drive.
+    pub drive: Drive,
+    // rustdoc-stripper-ignore-next
+    /// The volumes on the drive.
+    ///
+    /// This is empty for drives without media or with media that has no volumes.
+    pub volumes: Vec<VolumeEntry>,
+}
+
+// rustdoc-stripper-ignore-next
+/// A volume together with its mount, if it is mounted.
+#[derive(Debug, Clone)]
+pub struct VolumeEntry {
+    // rustdoc-stripper-ignore-next
+    /// The volume.
+    ///
+    /// Volumes that don't belong to a drive are listed in
+    /// [`VolumeSnapshot::volumes_without_drive`] instead of a [`DriveEntry`].
+    pub volume: Volume,
+    // rustdoc-stripper-ignore-next
+    /// The mount of the volume, or `None` if the volume is not mounted.
+    pub mount: Option<Mount>,
+}
+
+impl VolumeEntry {
+    fn new(volume: Volume) -> Self {
+        Self {
+            mount: volume.get_mount(),
+            volume,
+        }
+    }
+}
+
+fn next_emission<O: IsA<glib::Object>, T: 'static>(
+    object: &O,
+    connect: impl FnOnce(&O, Box<dyn Fn(T)>) -> SignalHandlerId,
+) -> Pin<Box<dyn Future<Output = Option<T>> + 'static>> {
+    let (sender, receiver) = oneshot::channel();
+    let sender = RefCell::new(Some(sender));
+    let handler = connect(
+        object,
+        Box::new(move |value| {
+            if let Some(sender) = sender.borrow_mut().take() {
+                let _ = sender.send(value);
+            }
+        }),
+    );
+
+    Box::pin(NextEmission {
+        receiver,
+        object: object.upcast_ref().clone(),
+        handler: Some(handler),
+    })
+}
+
+// The future keeps a strong reference to the object so that the handler, and with it the
+// sender, stays alive until the future resolves or is dropped.
+struct NextEmission<T> {
+    receiver: oneshot::Receiver<T>,
+    object: glib::Object,
+    handler: Option<SignalHandlerId>,
+}
+
+impl<T> NextEmission<T> {
+    fn disconnect(&mut self) {
+        if let Some(handler) = self.handler.take() {
+            self.object.disconnect(handler);
+        }
+    }
+}
+
+impl<T> Future for NextEmission<T> {
+    type Output = Option<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        match Pin::new(&mut self.receiver).poll(cx) {
+            Poll::Ready(res) => {
+                self.disconnect();
+                // The sender is only dropped without sending if the handler was disconnected
+                Poll::Ready(res.ok())
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<T> Drop for NextEmission<T> {
+    fn drop(&mut self) {
+        self.disconnect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SimpleAction;
+
+    #[test]
+    fn next_emission_resolves() {
+        let action = SimpleAction::new("test", None);
+        let fut = next_emission(&action, |action, f| {
+            action.connect_activate(move |_, _| f(()))
+        });
+        action.activate(None);
+        assert_eq!(glib::MainContext::new().block_on(fut), Some(()));
+
+        // The handler was disconnected when the future resolved
+        action.activate(None);
+    }
+
+    #[test]
+    fn next_emission_disconnected() {
+        let action = SimpleAction::new("test", None);
+        // Dropping the callback is what happens when the handler is disconnected externally
+        let fut = next_emission::<_, ()>(&action, |action, f| {
+            drop(f);
+            action.connect_activate(|_, _| ())
+        });
+        action.activate(None);
+        assert_eq!(glib::MainContext::new().block_on(fut), None);
+    }
+}
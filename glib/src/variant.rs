@@ -149,13 +149,20 @@ unsafe impl<'a> crate::value::FromValue<'a> for Variant {
     type Checker = crate::value::GenericValueTypeOrNoneChecker<Self>;
 
     unsafe fn from_value(value: &'a crate::Value) -> Self {
+        // NULL variants are rejected by the checker, and `Option<Variant>` maps them to `None`
         let ptr = gobject_ffi::g_value_dup_variant(value.to_glib_none().0);
         debug_assert!(!ptr.is_null());
         from_glib_full(ptr)
     }
 }
 
-#[doc(hidden)]
+// rustdoc-stripper-ignore-next
+/// Stores the variant in a [`Value`](crate::Value).
+///
+/// Variants are immutable and reference counted, so this only takes a new reference and never
+/// copies the data. The `Value` has to hold its own reference as it may outlive the borrow,
+/// which is why there is no variant of this that avoids the reference. Converting an owned
+/// `Variant` with `Value::from()` moves the existing reference into the `Value` instead.
 impl crate::value::ToValue for Variant {
     fn to_value(&self) -> crate::Value {
         unsafe {
@@ -170,7 +177,8 @@ impl crate::value::ToValue for Variant {
     }
 }
 
-#[doc(hidden)]
+// rustdoc-stripper-ignore-next
+/// Moves the variant into a [`Value`](crate::Value) without taking another reference.
 impl From<Variant> for crate::Value {
     #[inline]
     fn from(v: Variant) -> Self {
@@ -182,6 +190,20 @@ impl From<Variant> for crate::Value {
     }
 }
 
+// rustdoc-stripper-ignore-next
+/// Gets the variant stored in a [`Value`](crate::Value).
+///
+/// This fails if the value doesn't hold a variant or if it holds a `NULL` variant. Use
+/// `value.get::<Option<Variant>>()` to allow the latter.
+impl TryFrom<&crate::Value> for Variant {
+    type Error = crate::value::ValueTypeMismatchOrNoneError<crate::value::ValueTypeMismatchError>;
+
+    #[inline]
+    fn try_from(value: &crate::Value) -> Result<Self, Self::Error> {
+        value.get()
+    }
+}
+
 #[doc(hidden)]
 impl crate::value::ToValueOptional for Variant {
     fn to_value_optional(s: Option<&Self>) -> crate::Value {
@@ -197,30 +219,150 @@ impl crate::value::ToValueOptional for Variant {
 // rustdoc-stripper-ignore-next
 /// An error returned from the [`try_get`](struct.Variant.html#method.try_get) function
 /// on a [`Variant`](struct.Variant.html) when the expected type does not match the actual type.
+///
+/// Use [`VariantTypeMismatchError::new()`] to create one, as more fields may be added in the
+/// future.
+///
+/// If a definite type was required but only an indefinite one was available, e.g. when
+/// creating a maybe Nothing of an indefinite type, `actual` is that indefinite type.
 #[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
 pub struct VariantTypeMismatchError {
     pub actual: VariantType,
     pub expected: VariantType,
+    // rustdoc-stripper-ignore-next
+    /// Location of the mismatching value inside the converted variant, from the outside in.
+    ///
+    /// Segments are `.n` for tuple fields, `[n]` for array elements and `{key}` for dictionary
+    /// values. The path is empty if the top-level variant itself didn't match.
+    pub path: Vec<String>,
 }
 
 impl VariantTypeMismatchError {
     pub fn new(actual: VariantType, expected: VariantType) -> Self {
-        Self { actual, expected }
+        Self {
+            actual,
+            expected,
+            path: Vec::new(),
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the index of the tuple field that failed to convert, if any.
+    ///
+    /// This is taken from the first segment of [`path`](Self::path), so for nested tuples it is
+    /// the index in the outermost tuple.
+    pub fn field(&self) -> Option<usize> {
+        self.path.first()?.strip_prefix('.')?.parse().ok()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Prepends a segment to the path of the mismatching value.
+    ///
+    /// Containers call this with the location of the failing child while the error propagates
+    /// outwards.
+    #[must_use]
+    pub fn with_path_prefix(mut self, segment: impl Into<String>) -> Self {
+        self.path.insert(0, segment.into());
+        self
+    }
+
+    fn indefinite(type_: VariantType) -> Self {
+        Self::new(type_.clone(), type_)
+    }
+
+    fn for_variant<T: StaticVariantType>(variant: &Variant) -> Self {
+        Self::new(
+            variant.type_().to_owned(),
+            T::static_variant_type().into_owned(),
+        )
     }
 }
 
 impl fmt::Display for VariantTypeMismatchError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "Type mismatch: Expected '{}' got '{}'",
-            self.expected, self.actual
-        )
+        if !self.path.is_empty() {
+            write!(f, "Type mismatch at {}: ", self.path.concat())?;
+        } else {
+            f.write_str("Type mismatch: ")?;
+        }
+
+        if !self.actual.is_definite() {
+            // Types of actual values are always definite
+            write!(f, "Expected a definite type got '{}'", self.actual)
+        } else {
+            write!(f, "Expected '{}' got '{}'", self.expected, self.actual)
+        }
     }
 }
 
 impl std::error::Error for VariantTypeMismatchError {}
 
+// rustdoc-stripper-ignore-next
+/// An error returned from [`Variant::child_get_checked()`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum VariantChildError {
+    // rustdoc-stripper-ignore-next
+    /// The variant is not a container.
+    NotContainer(VariantType),
+    // rustdoc-stripper-ignore-next
+    /// The container has no child at `index`.
+    OutOfRange { index: usize, n_children: usize },
+    // rustdoc-stripper-ignore-next
+    /// The child has a different type than expected.
+    TypeMismatch(VariantTypeMismatchError),
+}
+
+impl fmt::Display for VariantChildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotContainer(type_) => write!(f, "Variant of type '{type_}' is not a container"),
+            Self::OutOfRange { index, n_children } => write!(
+                f,
+                "Child index {index} out of range for container with {n_children} children"
+            ),
+            Self::TypeMismatch(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for VariantChildError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::TypeMismatch(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<VariantTypeMismatchError> for VariantChildError {
+    fn from(err: VariantTypeMismatchError) -> Self {
+        Self::TypeMismatch(err)
+    }
+}
+
+macro_rules! scalar_getter {
+    ($name:ident, $ty:ty, $type_str:literal, $get_fn:ident, $alias:literal) => {
+        scalar_getter!($name, $ty, $type_str, $get_fn, $alias, std::convert::identity);
+    };
+    ($name:ident, $ty:ty, $type_str:literal, $get_fn:ident, $alias:literal, $from_ffi:expr) => {
+        // rustdoc-stripper-ignore-next
+        #[doc = concat!("Returns the value if the variant has type `", $type_str, "`, `None` otherwise.")]
+        ///
+        /// Unlike [`Self::get()`] this only compares the type string, which makes it suitable for
+        /// tight loops over homogeneous data.
+        #[doc(alias = $alias)]
+        #[inline]
+        pub fn $name(&self) -> Option<$ty> {
+            if self.type_string() == $type_str {
+                Some(unsafe { $from_ffi(ffi::$get_fn(self.to_glib_none().0)) })
+            } else {
+                None
+            }
+        }
+    };
+}
+
 impl Variant {
     // rustdoc-stripper-ignore-next
     /// Returns the type of the value.
@@ -229,6 +371,30 @@ impl Variant {
         unsafe { VariantTy::from_ptr(ffi::g_variant_get_type(self.to_glib_none().0)) }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Returns the type string of the value.
+    ///
+    /// This is the same as `self.type_().as_str()`.
+    #[doc(alias = "g_variant_get_type_string")]
+    #[doc(alias = "get_type_string")]
+    pub fn type_string(&self) -> &str {
+        unsafe {
+            let ptr = ffi::g_variant_get_type_string(self.to_glib_none().0);
+            // Type strings are always ASCII
+            str::from_utf8_unchecked(std::ffi::CStr::from_ptr(ptr).to_bytes())
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the element type if the value is an array.
+    ///
+    /// Returns `None` for all other types.
+    #[doc(alias = "g_variant_type_element")]
+    pub fn array_element_type(&self) -> Option<&VariantTy> {
+        let type_ = self.type_();
+        type_.is_array().then(|| type_.element())
+    }
+
     // rustdoc-stripper-ignore-next
     /// Returns `true` if the type of the value corresponds to `T`.
     #[inline]
@@ -252,6 +418,26 @@ impl Variant {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Returns `true` if the type of the value is exactly `type_`.
+    ///
+    /// Unlike [`Self::is_type()`] and [`Self::is()`] this doesn't accept subtypes. As the type
+    /// of a value is always definite, this returns `false` for indefinite types like `a{?*}`
+    /// or `*`, which `is_type()` matches.
+    pub fn is_exact_type(&self, type_: &VariantTy) -> bool {
+        self.type_() == type_
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns `true` if the variant has the type of `T` and holds `value`.
+    ///
+    /// This reads the variant with [`FromVariant`] instead of converting `value` into a second
+    /// variant, so comparing against basic values like integers doesn't allocate. To compare
+    /// against a string slice compare the variant with `==` directly.
+    pub fn eq_value<T: FromVariant + PartialEq>(&self, value: &T) -> bool {
+        self.get::<T>().is_some_and(|v| v == *value)
+    }
+
     // rustdoc-stripper-ignore-next
     /// Returns the classification of the variant.
     #[doc(alias = "g_variant_classify")]
@@ -259,6 +445,39 @@ impl Variant {
         unsafe { from_glib(ffi::g_variant_classify(self.to_glib_none().0)) }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Compares two variants of the same basic type.
+    ///
+    /// This is meant for sorting columns of values that are known to have the same basic type,
+    /// see [`VariantTy::is_basic()`]. It only compares the classes of both variants, which for
+    /// basic types implies that the types are equal, before calling `g_variant_compare()`.
+    ///
+    /// Returns `None` if the classes differ or are not basic.
+    #[doc(alias = "g_variant_compare")]
+    pub fn compare_basic(&self, other: &Variant) -> Option<Ordering> {
+        unsafe {
+            let class = ffi::g_variant_classify(self.to_glib_none().0);
+            if class != ffi::g_variant_classify(other.to_glib_none().0)
+                || matches!(
+                    class,
+                    ffi::G_VARIANT_CLASS_VARIANT
+                        | ffi::G_VARIANT_CLASS_MAYBE
+                        | ffi::G_VARIANT_CLASS_ARRAY
+                        | ffi::G_VARIANT_CLASS_TUPLE
+                        | ffi::G_VARIANT_CLASS_DICT_ENTRY
+                )
+            {
+                return None;
+            }
+
+            let res = ffi::g_variant_compare(
+                ToGlibPtr::<*const _>::to_glib_none(self).0 as *const _,
+                ToGlibPtr::<*const _>::to_glib_none(other).0 as *const _,
+            );
+            Some(res.cmp(&0))
+        }
+    }
+
     // rustdoc-stripper-ignore-next
     /// Tries to extract a value of type `T`.
     ///
@@ -270,13 +489,76 @@ impl Variant {
 
     // rustdoc-stripper-ignore-next
     /// Tries to extract a value of type `T`.
+    ///
+    /// If the value is a container, the error describes the first child that failed to convert.
+    #[inline]
     pub fn try_get<T: FromVariant>(&self) -> Result<T, VariantTypeMismatchError> {
-        self.get().ok_or_else(|| {
-            VariantTypeMismatchError::new(
+        T::try_from_variant(self)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Tries to extract a value of type `T`, consuming the variant.
+    ///
+    /// On failure the variant is handed back together with the error, so that a different
+    /// type can be tried without cloning it first.
+    pub fn into_get<T: FromVariant>(self) -> Result<T, (Variant, VariantTypeMismatchError)> {
+        match T::try_from_variant(&self) {
+            Ok(value) => Ok(value),
+            Err(err) => Err((self, err)),
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns `self` if the value has the type of `T`, an error otherwise.
+    ///
+    /// This allows checking the type with `?` before reading the value.
+    ///
+    /// ```
+    /// # use glib::prelude::*;
+    /// let v = 5u32.to_variant();
+    /// assert_eq!(v.expect_type::<u32>()?.get::<u32>(), Some(5));
+    /// assert!(v.expect_type::<String>().is_err());
+    /// # Ok::<(), glib::variant::VariantTypeMismatchError>(())
+    /// ```
+    pub fn expect_type<T: StaticVariantType>(&self) -> Result<&Self, VariantTypeMismatchError> {
+        if self.is::<T>() {
+            Ok(self)
+        } else {
+            Err(VariantTypeMismatchError::for_variant::<T>(self))
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Checks that the value has type `expected`, for types only known at runtime.
+    ///
+    /// This is the runtime analogue of the type check done by [`Self::try_get()`]. Like
+    /// [`Self::is_type()`] an indefinite `expected` type like `a*` matches all of its
+    /// subtypes, use [`Self::is_exact_type()`] to rule those out.
+    #[doc(alias = "g_variant_is_of_type")]
+    pub fn check_type(&self, expected: &VariantTy) -> Result<(), VariantTypeMismatchError> {
+        if self.is_type(expected) {
+            Ok(())
+        } else {
+            Err(VariantTypeMismatchError::new(
                 self.type_().to_owned(),
-                T::static_variant_type().into_owned(),
-            )
-        })
+                expected.to_owned(),
+            ))
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Tries to extract a value of type `T`, returning `default` if the type does not match.
+    #[inline]
+    pub fn get_or<T: FromVariant>(&self, default: T) -> T {
+        self.get().unwrap_or(default)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Tries to extract a value of type `T`, computing a fallback with `f` if the type does not
+    /// match.
+    #[inline]
+    pub fn get_or_else<T: FromVariant, F: FnOnce() -> T>(&self, f: F) -> T {
+        self.get().unwrap_or_else(f)
     }
 
     // rustdoc-stripper-ignore-next
@@ -328,6 +610,26 @@ impl Variant {
         Some(v)
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Shorthand for [`Self::child_value`].
+    ///
+    /// # Panics
+    ///
+    /// * if `self` is not a container type.
+    /// * if given `index` is larger than number of children.
+    #[inline]
+    #[must_use]
+    pub fn at(&self, index: usize) -> Variant {
+        self.child_value(index)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Shorthand for [`Self::try_child_value`].
+    #[inline]
+    pub fn get_at(&self, index: usize) -> Option<Variant> {
+        self.try_child_value(index)
+    }
+
     // rustdoc-stripper-ignore-next
     /// Try to read a child item out of a container `Variant` instance.
     ///
@@ -343,6 +645,29 @@ impl Variant {
         self.try_child_value(index).map(|v| v.try_get()).transpose()
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Read a child item out of a container `Variant` instance.
+    ///
+    /// Unlike [`Self::try_child_get()`] this distinguishes a missing child from a child of the
+    /// wrong type: it returns [`VariantChildError::NotContainer`] if `self` is not a container,
+    /// [`VariantChildError::OutOfRange`] if there is no child at `index` and
+    /// [`VariantChildError::TypeMismatch`] if the child is not of type `T`.
+    pub fn child_get_checked<T: StaticVariantType + FromVariant>(
+        &self,
+        index: usize,
+    ) -> Result<T, VariantChildError> {
+        if !self.is_container() {
+            return Err(VariantChildError::NotContainer(self.type_().to_owned()));
+        }
+
+        let n_children = self.n_children();
+        if index >= n_children {
+            return Err(VariantChildError::OutOfRange { index, n_children });
+        }
+
+        Ok(self.child_value(index).try_get()?)
+    }
+
     // rustdoc-stripper-ignore-next
     /// Read a child item out of a container `Variant` instance.
     ///
@@ -385,6 +710,62 @@ impl Variant {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Tries to extract a `&str` from a plain string.
+    ///
+    /// Unlike [`Self::str()`] this returns `Some` only if the variant has type `s`, not for
+    /// object paths or signatures. Use `get::<ObjectPath>()` or `get::<Signature>()` to
+    /// require those instead.
+    #[doc(alias = "g_variant_get_string")]
+    pub fn str_exact(&self) -> Option<&str> {
+        if self.type_string() == "s" {
+            self.str()
+        } else {
+            None
+        }
+    }
+
+    scalar_getter!(
+        boolean,
+        bool,
+        "b",
+        g_variant_get_boolean,
+        "g_variant_get_boolean",
+        from_glib::<ffi::gboolean, bool>
+    );
+    scalar_getter!(byte, u8, "y", g_variant_get_byte, "g_variant_get_byte");
+    scalar_getter!(int16, i16, "n", g_variant_get_int16, "g_variant_get_int16");
+    scalar_getter!(
+        uint16,
+        u16,
+        "q",
+        g_variant_get_uint16,
+        "g_variant_get_uint16"
+    );
+    scalar_getter!(int32, i32, "i", g_variant_get_int32, "g_variant_get_int32");
+    scalar_getter!(
+        uint32,
+        u32,
+        "u",
+        g_variant_get_uint32,
+        "g_variant_get_uint32"
+    );
+    scalar_getter!(int64, i64, "x", g_variant_get_int64, "g_variant_get_int64");
+    scalar_getter!(
+        uint64,
+        u64,
+        "t",
+        g_variant_get_uint64,
+        "g_variant_get_uint64"
+    );
+    scalar_getter!(
+        double,
+        f64,
+        "d",
+        g_variant_get_double,
+        "g_variant_get_double"
+    );
+
     // rustdoc-stripper-ignore-next
     /// Tries to extract a `&[T]` from a variant of array type with a suitable element type.
     ///
@@ -394,10 +775,10 @@ impl Variant {
         unsafe {
             let expected_ty = T::static_variant_type().as_array();
             if self.type_() != expected_ty {
-                return Err(VariantTypeMismatchError {
-                    actual: self.type_().to_owned(),
-                    expected: expected_ty.into_owned(),
-                });
+                return Err(VariantTypeMismatchError::new(
+                    self.type_().to_owned(),
+                    expected_ty.into_owned(),
+                ));
             }
 
             let mut n_elements = mem::MaybeUninit::uninit();
@@ -417,12 +798,30 @@ impl Variant {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Copies the elements of a fixed-size array variant into `out`.
+    ///
+    /// At most `out.len()` elements are copied, and the number of copied elements is
+    /// returned. Returns an error if the type is wrong, like [`Self::fixed_array()`].
+    #[doc(alias = "g_variant_get_fixed_array")]
+    pub fn copy_fixed_array<T: FixedSizeVariantType>(
+        &self,
+        out: &mut [T],
+    ) -> Result<usize, VariantTypeMismatchError> {
+        let data = self.fixed_array::<T>()?;
+        let n = data.len().min(out.len());
+        out[..n].copy_from_slice(&data[..n]);
+
+        Ok(n)
+    }
+
     // rustdoc-stripper-ignore-next
     /// Creates a new Variant array from children.
     ///
     /// # Panics
     ///
-    /// This function panics if not all variants are of type `T`.
+    /// This function panics if not all variants are of type `T`. See
+    /// [`Self::try_array_from_iter`] for a non-panicking version.
     #[doc(alias = "g_variant_new_array")]
     pub fn array_from_iter<T: StaticVariantType>(
         children: impl IntoIterator<Item = Variant>,
@@ -430,17 +829,144 @@ impl Variant {
         Self::array_from_iter_with_type(&T::static_variant_type(), children)
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Creates a new Variant array from children.
+    ///
+    /// Returns an error for the first child that is not of type `T`.
+    #[doc(alias = "g_variant_new_array")]
+    pub fn try_array_from_iter<T: StaticVariantType>(
+        children: impl IntoIterator<Item = Variant>,
+    ) -> Result<Self, VariantTypeMismatchError> {
+        Self::try_array_from_iter_with_type(&T::static_variant_type(), children)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a new Variant array by converting each item with [`ToVariant`].
+    ///
+    /// This is a shorthand for mapping the items through [`ToVariant::to_variant()`]
+    /// before calling [`Self::array_from_iter()`].
+    #[doc(alias = "g_variant_new_array")]
+    pub fn from_array_iter<T: ToVariant + StaticVariantType>(
+        iter: impl IntoIterator<Item = T>,
+    ) -> Self {
+        Self::array_from_iter::<T>(iter.into_iter().map(|v| v.to_variant()))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a new dictionary (`a{kv}`) Variant from key-value pairs.
+    ///
+    /// Unlike the conversions from `HashMap` and `BTreeMap` the entries are kept in the order
+    /// they are yielded, which gives deterministic output. Duplicate keys are not removed.
+    #[doc(alias = "g_variant_new_array")]
+    pub fn from_dict_entries<K, V>(entries: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: StaticVariantType + ToVariant,
+        V: StaticVariantType + ToVariant,
+    {
+        Self::array_from_iter::<DictEntry<K, V>>(
+            entries
+                .into_iter()
+                .map(|(k, v)| Self::from_dict_entry(&k.to_variant(), &v.to_variant())),
+        )
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a new Variant from a dynamically typed [`Value`](crate::variant::Value) tree.
+    ///
+    /// The element types of arrays and dictionaries are inferred from their first element.
+    ///
+    /// Returns an error if
+    /// * the elements of an array or the entries of a dictionary have different types,
+    /// * an array or dictionary is empty, as its element type can't be inferred, or
+    /// * a dictionary key is not a basic value.
+    pub fn from_dynamic(value: &Value) -> Result<Self, crate::BoolError> {
+        fn homogeneous_array(children: Vec<Variant>) -> Result<Variant, crate::BoolError> {
+            let Some(first) = children.first() else {
+                return Err(bool_error!(
+                    "Can't infer the element type of an empty array"
+                ));
+            };
+
+            let type_ = first.type_();
+            if let Some((i, child)) = children
+                .iter()
+                .enumerate()
+                .find(|(_, child)| child.type_() != type_)
+            {
+                return Err(bool_error!(
+                    "Array element {} has type '{}' instead of '{}'",
+                    i,
+                    child.type_(),
+                    type_
+                ));
+            }
+
+            Ok(Variant::array_from_iter_with_type(type_, &children))
+        }
+
+        match value {
+            Value::Bool(v) => Ok(v.to_variant()),
+            Value::I64(v) => Ok(v.to_variant()),
+            Value::U64(v) => Ok(v.to_variant()),
+            Value::F64(v) => Ok(v.to_variant()),
+            Value::Str(v) => Ok(v.to_variant()),
+            Value::Array(values) => homogeneous_array(
+                values
+                    .iter()
+                    .map(Self::from_dynamic)
+                    .collect::<Result<_, _>>()?,
+            ),
+            Value::Dict(entries) => homogeneous_array(
+                entries
+                    .iter()
+                    .map(|(key, value)| {
+                        let key = Self::from_dynamic(key)?;
+                        if !key.type_().is_basic() {
+                            return Err(bool_error!(
+                                "Dictionary key of type '{}' is not a basic type",
+                                key.type_()
+                            ));
+                        }
+                        Ok(Self::from_dict_entry(&key, &Self::from_dynamic(value)?))
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            Value::Tuple(values) => Ok(Self::tuple_from_iter(
+                values
+                    .iter()
+                    .map(Self::from_dynamic)
+                    .collect::<Result<Vec<_>, _>>()?,
+            )),
+        }
+    }
+
     // rustdoc-stripper-ignore-next
     /// Creates a new Variant array from children with the specified type.
     ///
     /// # Panics
     ///
-    /// This function panics if not all variants are of type `type_`.
+    /// This function panics if not all variants are of type `type_`. See
+    /// [`Self::try_array_from_iter_with_type`] for a non-panicking version.
     #[doc(alias = "g_variant_new_array")]
     pub fn array_from_iter_with_type(
         type_: &VariantTy,
         children: impl IntoIterator<Item = impl AsRef<Variant>>,
     ) -> Self {
+        match Self::try_array_from_iter_with_type(type_, children) {
+            Ok(v) => v,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a new Variant array from children with the specified type.
+    ///
+    /// Returns an error for the first child that is not of type `type_`.
+    #[doc(alias = "g_variant_new_array")]
+    pub fn try_array_from_iter_with_type(
+        type_: &VariantTy,
+        children: impl IntoIterator<Item = impl AsRef<Variant>>,
+    ) -> Result<Self, VariantTypeMismatchError> {
         unsafe {
             let mut builder = mem::MaybeUninit::uninit();
             ffi::g_variant_builder_init(builder.as_mut_ptr(), type_.as_array().to_glib_none().0);
@@ -451,43 +977,197 @@ impl Variant {
                     == ffi::GFALSE
                 {
                     ffi::g_variant_builder_clear(&mut builder);
-                    assert!(value.is_type(type_));
+                    return Err(VariantTypeMismatchError::new(
+                        value.type_().to_owned(),
+                        type_.to_owned(),
+                    ));
                 }
 
                 ffi::g_variant_builder_add_value(&mut builder, value.to_glib_none().0);
             }
-            from_glib_none(ffi::g_variant_builder_end(&mut builder))
+            Ok(from_glib_none(ffi::g_variant_builder_end(&mut builder)))
         }
     }
 
     // rustdoc-stripper-ignore-next
-    /// Creates a new Variant array from a fixed array.
-    #[doc(alias = "g_variant_new_fixed_array")]
-    pub fn array_from_fixed_array<T: FixedSizeVariantType>(array: &[T]) -> Self {
-        let type_ = T::static_variant_type();
+    /// Creates a new Variant array by applying `f` to each child of the array `self`.
+    ///
+    /// The element type of the new array is the type of the first mapped child, so `f` may
+    /// change the element type, e.g. turn an `ai` of IDs into an `as` of names.
+    ///
+    /// Returns an error if `self` is not an array or if the mapped children don't all have
+    /// the same type, with the path pointing at the first differing child.
+    ///
+    /// As the element type can't be inferred from an empty array, that is reported as an error
+    /// as well. Its `actual` type is the indefinite `a*`.
+    pub fn map_array<F: FnMut(Variant) -> Variant>(
+        &self,
+        f: F,
+    ) -> Result<Variant, VariantTypeMismatchError> {
+        if !self.type_().is_array() {
+            return Err(VariantTypeMismatchError::new(
+                self.type_().to_owned(),
+                VariantType::new_array(VariantTy::ANY),
+            ));
+        }
+        if self.n_children() == 0 {
+            return Err(VariantTypeMismatchError::indefinite(
+                VariantType::new_array(VariantTy::ANY),
+            ));
+        }
 
-        unsafe {
-            from_glib_none(ffi::g_variant_new_fixed_array(
-                type_.as_ptr(),
-                array.as_ptr() as ffi::gconstpointer,
-                array.len(),
-                mem::size_of::<T>(),
-            ))
+        let children = self.iter().map(f).collect::<Vec<_>>();
+        let type_ = children[0].type_();
+        if let Some((i, child)) = children
+            .iter()
+            .enumerate()
+            .find(|(_, child)| child.type_() != type_)
+        {
+            return Err(
+                VariantTypeMismatchError::new(child.type_().to_owned(), type_.to_owned())
+                    .with_path_prefix(format!("[{i}]")),
+            );
         }
+
+        Ok(Self::array_from_iter_with_type(type_, &children))
     }
 
     // rustdoc-stripper-ignore-next
-    /// Creates a new Variant tuple from children.
-    #[doc(alias = "g_variant_new_tuple")]
-    pub fn tuple_from_iter(children: impl IntoIterator<Item = impl AsRef<Variant>>) -> Self {
-        unsafe {
-            let mut builder = mem::MaybeUninit::uninit();
-            ffi::g_variant_builder_init(builder.as_mut_ptr(), VariantTy::TUPLE.to_glib_none().0);
-            let mut builder = builder.assume_init();
-            for value in children.into_iter() {
-                ffi::g_variant_builder_add_value(&mut builder, value.as_ref().to_glib_none().0);
-            }
-            from_glib_none(ffi::g_variant_builder_end(&mut builder))
+    /// Creates a new Variant array with the children of `self` followed by the children of
+    /// `other`.
+    ///
+    /// Returns an error if `self` is not an array or if `other` is not an array of the same
+    /// type.
+    pub fn concat_arrays(&self, other: &Variant) -> Result<Variant, VariantTypeMismatchError> {
+        let type_ = self.type_();
+        if !type_.is_array() {
+            return Err(VariantTypeMismatchError::new(
+                type_.to_owned(),
+                VariantTy::ARRAY.to_owned(),
+            ));
+        }
+        if other.type_() != type_ {
+            return Err(VariantTypeMismatchError::new(
+                other.type_().to_owned(),
+                type_.to_owned(),
+            ));
+        }
+
+        Ok(Self::array_from_iter_with_type(
+            type_.element(),
+            self.iter().chain(other.iter()),
+        ))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a copy of an array of basic values with its elements sorted.
+    ///
+    /// Elements are ordered like the [`PartialOrd`] implementation, i.e. by
+    /// `g_variant_compare()`, except for doubles which are ordered by [`f64::total_cmp`] to
+    /// handle NaN.
+    ///
+    /// Returns an error if `self` is not an array of a basic type, as containers have no total
+    /// order.
+    #[doc(alias = "g_variant_compare")]
+    pub fn sorted_array(&self) -> Result<Variant, VariantTypeMismatchError> {
+        let type_ = self.type_();
+        if !type_.is_array() || !type_.element().is_basic() {
+            return Err(VariantTypeMismatchError::new(
+                type_.to_owned(),
+                VariantType::new_array(VariantTy::BASIC),
+            ));
+        }
+
+        let element = type_.element();
+        let mut children = self.children();
+        if element == VariantTy::DOUBLE {
+            children.sort_by(|a, b| a.get::<f64>().unwrap().total_cmp(&b.get::<f64>().unwrap()));
+        } else {
+            children.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        }
+
+        Ok(Self::array_from_iter_with_type(element, children))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a new empty Variant array with the given element type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `element_ty` is not a definite type. See [`Self::try_empty_array`] for a
+    /// non-panicking version.
+    #[doc(alias = "g_variant_new_array")]
+    pub fn empty_array(element_ty: &VariantTy) -> Self {
+        match Self::try_empty_array(element_ty) {
+            Ok(v) => v,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a new empty Variant array with the given element type.
+    ///
+    /// Returns an error if `element_ty` is not a definite type, e.g. `*` or `r`.
+    #[doc(alias = "g_variant_new_array")]
+    pub fn try_empty_array(element_ty: &VariantTy) -> Result<Self, crate::BoolError> {
+        if !element_ty.is_definite() {
+            return Err(bool_error!(
+                "Array element type '{}' is not definite",
+                element_ty
+            ));
+        }
+
+        unsafe {
+            Ok(from_glib_none(ffi::g_variant_new_array(
+                element_ty.to_glib_none().0,
+                ptr::null(),
+                0,
+            )))
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a new Variant array from a fixed array.
+    #[doc(alias = "g_variant_new_fixed_array")]
+    pub fn array_from_fixed_array<T: FixedSizeVariantType>(array: &[T]) -> Self {
+        let type_ = T::static_variant_type();
+
+        unsafe {
+            from_glib_none(ffi::g_variant_new_fixed_array(
+                type_.as_ptr(),
+                array.as_ptr() as ffi::gconstpointer,
+                array.len(),
+                mem::size_of::<T>(),
+            ))
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a new Variant array from a `Vec` of fixed size elements, taking ownership of it.
+    ///
+    /// Unlike [`Self::array_from_fixed_array`], this does not copy the elements but uses the
+    /// buffer of the `Vec` as serialized data of the array.
+    #[doc(alias = "g_variant_new_from_data")]
+    pub fn from_fixed_vec<T: FixedSizeVariantType>(data: Vec<T>) -> Self {
+        FixedSizeVariantArray::<Vec<T>, T>::from(data).into()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a new Variant tuple from children.
+    ///
+    /// Unlike the array constructors, which require all children to have the same type, the
+    /// children may have different types. The tuple type is derived from them, so this can
+    /// build tuples whose shape is only known at runtime, e.g. `(sub)`.
+    #[doc(alias = "g_variant_new_tuple")]
+    pub fn tuple_from_iter(children: impl IntoIterator<Item = impl AsRef<Variant>>) -> Self {
+        unsafe {
+            let mut builder = mem::MaybeUninit::uninit();
+            ffi::g_variant_builder_init(builder.as_mut_ptr(), VariantTy::TUPLE.to_glib_none().0);
+            let mut builder = builder.assume_init();
+            for value in children.into_iter() {
+                ffi::g_variant_builder_add_value(&mut builder, value.as_ref().to_glib_none().0);
+            }
+            from_glib_none(ffi::g_variant_builder_end(&mut builder))
         }
     }
 
@@ -507,21 +1187,53 @@ impl Variant {
 
     // rustdoc-stripper-ignore-next
     /// Creates a new maybe Variant.
+    ///
+    /// If the element type is only known at runtime, use [`Self::from_some`] and
+    /// [`Self::from_none`] instead, which don't need a separate `T` that could disagree with the
+    /// child.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `child` is not of type `T`, or if `child` is `None` and `T` is not a definite
+    /// type. See [`Self::try_from_maybe`] for a non-panicking version.
     #[doc(alias = "g_variant_new_maybe")]
     pub fn from_maybe<T: StaticVariantType>(child: Option<&Variant>) -> Self {
+        match Self::try_from_maybe::<T>(child) {
+            Ok(v) => v,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a new maybe Variant.
+    ///
+    /// Returns an error if `child` is not of type `T`. If `child` is `None` and `T` is not a
+    /// definite type, the error's `actual` type is the indefinite type of `T`.
+    #[doc(alias = "g_variant_new_maybe")]
+    pub fn try_from_maybe<T: StaticVariantType>(
+        child: Option<&Variant>,
+    ) -> Result<Self, VariantTypeMismatchError> {
         let type_ = T::static_variant_type();
         match child {
             Some(child) => {
-                assert_eq!(type_, child.type_());
+                if type_ != child.type_() {
+                    return Err(VariantTypeMismatchError::new(
+                        child.type_().to_owned(),
+                        type_.into_owned(),
+                    ));
+                }
 
-                Self::from_some(child)
+                Ok(Self::from_some(child))
             }
-            None => Self::from_none(&type_),
+            None => Self::try_from_none(&type_)
+                .map_err(|_| VariantTypeMismatchError::indefinite(type_.into_owned())),
         }
     }
 
     // rustdoc-stripper-ignore-next
     /// Creates a new maybe Variant from a child.
+    ///
+    /// The element type of the maybe is the type of `child`.
     #[doc(alias = "g_variant_new_maybe")]
     pub fn from_some(child: &Variant) -> Self {
         unsafe {
@@ -534,13 +1246,34 @@ impl Variant {
 
     // rustdoc-stripper-ignore-next
     /// Creates a new maybe Variant with Nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `type_` is not a definite type. See [`Self::try_from_none`] for a
+    /// non-panicking version.
     #[doc(alias = "g_variant_new_maybe")]
     pub fn from_none(type_: &VariantTy) -> Self {
+        match Self::try_from_none(type_) {
+            Ok(v) => v,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a new maybe Variant with Nothing.
+    ///
+    /// Returns an error if `type_` is not a definite type, e.g. `*` or `r`.
+    #[doc(alias = "g_variant_new_maybe")]
+    pub fn try_from_none(type_: &VariantTy) -> Result<Self, crate::BoolError> {
+        if !type_.is_definite() {
+            return Err(bool_error!("Maybe child type '{}' is not definite", type_));
+        }
+
         unsafe {
-            from_glib_none(ffi::g_variant_new_maybe(
+            Ok(from_glib_none(ffi::g_variant_new_maybe(
                 type_.to_glib_none().0,
                 ptr::null_mut(),
-            ))
+            )))
         }
     }
 
@@ -559,6 +1292,35 @@ impl Variant {
         unsafe { from_glib_full(ffi::g_variant_get_maybe(self.to_glib_none().0)) }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Creates a [`VariantDict`](crate::VariantDict) from a vardict (`a{sv}`) variant.
+    ///
+    /// The dictionary allows looking up entries by key. Returns `None` if the variant has
+    /// any other type.
+    #[doc(alias = "g_variant_dict_new")]
+    pub fn to_dict(&self) -> Option<crate::VariantDict> {
+        self.is_exact_type(VariantTy::VARDICT)
+            .then(|| crate::VariantDict::new(Some(self)))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Extract the value of a maybe Variant.
+    ///
+    /// Returns `None` if the variant is not maybe-typed, `Some(None)` if the value is Nothing and
+    /// `Some(Some(child))` otherwise.
+    #[doc(alias = "g_variant_get_maybe")]
+    pub fn maybe_value(&self) -> Option<Option<Variant>> {
+        if !self.type_().is_maybe() {
+            return None;
+        }
+
+        unsafe {
+            Some(from_glib_full(ffi::g_variant_get_maybe(
+                self.to_glib_none().0,
+            )))
+        }
+    }
+
     // rustdoc-stripper-ignore-next
     /// Pretty-print the contents of this variant in a human-readable form.
     ///
@@ -573,6 +1335,71 @@ impl Variant {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Pretty-print the contents of this variant over multiple lines.
+    ///
+    /// Containers are expanded with one child per line, indented by `indent` spaces per
+    /// nesting level. Dictionary entries are printed as `key: value`, boxed variants as
+    /// `<value>` and maybes as either their value or `nothing`. Scalars and empty containers
+    /// are printed like [`print(true)`](Self::print).
+    ///
+    /// Unlike [`print()`](Self::print) the output is meant for humans and can't necessarily
+    /// be parsed back.
+    pub fn print_pretty(&self, indent: usize) -> String {
+        let mut out = String::new();
+        self.write_pretty(&mut out, indent, 0);
+        out
+    }
+
+    fn write_pretty(&self, out: &mut String, indent: usize, depth: usize) {
+        let type_ = self.type_();
+
+        if type_.is_variant() {
+            out.push('<');
+            self.as_variant().unwrap().write_pretty(out, indent, depth);
+            out.push('>');
+        } else if type_.is_maybe() {
+            match self.as_maybe() {
+                Some(child) => child.write_pretty(out, indent, depth),
+                None => out.push_str("nothing"),
+            }
+        } else if type_.is_dict_entry() {
+            out.push('{');
+            self.child_value(0).write_pretty(out, indent, depth);
+            out.push_str(": ");
+            self.child_value(1).write_pretty(out, indent, depth);
+            out.push('}');
+        } else if (type_.is_array() || type_.is_tuple()) && self.n_children() > 0 {
+            let is_dict = type_.is_array() && type_.element().is_dict_entry();
+            let (open, close) = if is_dict {
+                ('{', '}')
+            } else if type_.is_array() {
+                ('[', ']')
+            } else {
+                ('(', ')')
+            };
+
+            out.push(open);
+            for child in self.iter() {
+                out.push('\n');
+                out.extend(std::iter::repeat(' ').take(indent * (depth + 1)));
+                if is_dict {
+                    child.child_value(0).write_pretty(out, indent, depth + 1);
+                    out.push_str(": ");
+                    child.child_value(1).write_pretty(out, indent, depth + 1);
+                } else {
+                    child.write_pretty(out, indent, depth + 1);
+                }
+                out.push(',');
+            }
+            out.push('\n');
+            out.extend(std::iter::repeat(' ').take(indent * depth));
+            out.push(close);
+        } else {
+            out.push_str(&self.print(true));
+        }
+    }
+
     // rustdoc-stripper-ignore-next
     /// Parses a GVariant from the text representation produced by [`print()`](Self::print).
     #[doc(alias = "g_variant_parse")]
@@ -597,6 +1424,62 @@ impl Variant {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Formats an error returned by [`parse()`](Self::parse) for display.
+    ///
+    /// The result contains the error message and the offending part of `source`, which must be
+    /// the text that failed to parse, with the error location marked.
+    ///
+    /// Returns `None` if `error` is not a variant parse error or doesn't carry a location.
+    #[doc(alias = "g_variant_parse_error_print_context")]
+    pub fn parse_error_print_context(error: &crate::Error, source: &str) -> Option<crate::GString> {
+        unsafe {
+            if error.domain().into_glib() != ffi::g_variant_parse_error_quark() {
+                return None;
+            }
+
+            from_glib_full(ffi::g_variant_parse_error_print_context(
+                mut_override(error.to_glib_none().0),
+                source.to_glib_none().0,
+            ))
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a new bytestring (`ay`) variant from the UTF-8 bytes of `s`.
+    ///
+    /// Like all GLib bytestrings the array includes the trailing NUL byte. Interior NUL bytes
+    /// are kept, but note that readers of the bytestring as a C string stop at the first one.
+    #[doc(alias = "g_variant_new_bytestring")]
+    pub fn bytestring_from_str(s: &str) -> Self {
+        let mut tmp = Vec::with_capacity(s.len() + 1);
+        tmp.extend_from_slice(s.as_bytes());
+        tmp.push(0);
+        Self::array_from_fixed_array(&tmp)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a new `au` variant holding the Unicode scalar values of `s`.
+    ///
+    /// This is for interoperating with components that store text as UTF-32. See
+    /// [`Self::to_utf32_string()`] for the reverse direction.
+    pub fn from_utf32(s: &str) -> Self {
+        Self::from_fixed_vec(s.chars().map(u32::from).collect::<Vec<_>>())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Reads a string from an `au` variant of Unicode scalar values.
+    ///
+    /// Returns `None` if the variant has a different type or holds values that are not valid
+    /// Unicode scalar values, like surrogates.
+    pub fn to_utf32_string(&self) -> Option<String> {
+        self.fixed_array::<u32>()
+            .ok()?
+            .iter()
+            .map(|&c| char::from_u32(c))
+            .collect()
+    }
+
     // rustdoc-stripper-ignore-next
     /// Constructs a new serialized-mode GVariant instance.
     #[doc(alias = "g_variant_new_from_bytes")]
@@ -647,6 +1530,10 @@ impl Variant {
 
     // rustdoc-stripper-ignore-next
     /// Constructs a new serialized-mode GVariant instance with a given type.
+    ///
+    /// The variant keeps a reference to `bytes` instead of copying the data as long as it is
+    /// suitably aligned. Prefer this over [`Self::from_data_with_type()`] when the data is
+    /// already available as [`Bytes`].
     #[doc(alias = "g_variant_new_from_bytes")]
     pub fn from_bytes_with_type(bytes: &Bytes, type_: &VariantTy) -> Self {
         unsafe {
@@ -681,6 +1568,9 @@ impl Variant {
 
     // rustdoc-stripper-ignore-next
     /// Constructs a new serialized-mode GVariant instance with a given type.
+    ///
+    /// `data` is boxed and released once the variant is freed. If the data is already
+    /// available as [`Bytes`], [`Self::from_bytes_with_type()`] avoids that extra allocation.
     #[doc(alias = "g_variant_new_from_data")]
     pub fn from_data_with_type<A: AsRef<[u8]>>(data: A, type_: &VariantTy) -> Self {
         unsafe {
@@ -741,6 +1631,10 @@ impl Variant {
 
     // rustdoc-stripper-ignore-next
     /// Returns the serialized form of a GVariant instance.
+    ///
+    /// The returned `Bytes` share the variant's storage instead of copying it, i.e. they alias
+    /// the data returned by [`Self::data()`], and keep that storage alive independently of the
+    /// variant. Like `data()` this serializes the variant first if needed.
     #[doc(alias = "get_data_as_bytes")]
     #[doc(alias = "g_variant_get_data_as_bytes")]
     pub fn data_as_bytes(&self) -> Bytes {
@@ -762,6 +1656,24 @@ impl Variant {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Returns the serialized form of a GVariant instance if it has type `T`.
+    ///
+    /// Returns an error if the type of the variant is not exactly the type of `T`, which guards
+    /// against forwarding serialized data of an unexpected type.
+    #[doc(alias = "g_variant_get_data")]
+    pub fn bytes_if_type<T: StaticVariantType>(&self) -> Result<&[u8], VariantTypeMismatchError> {
+        let expected = T::static_variant_type();
+        if self.type_() != expected {
+            return Err(VariantTypeMismatchError::new(
+                self.type_().to_owned(),
+                expected.into_owned(),
+            ));
+        }
+
+        Ok(self.data())
+    }
+
     // rustdoc-stripper-ignore-next
     /// Returns the size of serialized form of a GVariant instance.
     #[doc(alias = "g_variant_get_size")]
@@ -787,6 +1699,20 @@ impl Variant {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Appends the serialized form of a GVariant instance to `out`.
+    ///
+    /// `out` is grown as needed and does not need to be aligned in any
+    /// particular way. Returns the number of bytes appended.
+    #[doc(alias = "g_variant_store")]
+    pub fn store_to_vec(&self, out: &mut Vec<u8>) -> usize {
+        // `g_variant_store()` requires the destination to be suitably aligned
+        // for the variant's type, which the end of `out` is not in general.
+        let data = self.data();
+        out.extend_from_slice(data);
+        data.len()
+    }
+
     // rustdoc-stripper-ignore-next
     /// Returns a copy of the variant in normal form.
     #[doc(alias = "g_variant_get_normal_form")]
@@ -795,6 +1721,73 @@ impl Variant {
         unsafe { from_glib_full(ffi::g_variant_get_normal_form(self.to_glib_none().0)) }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Converts the variant into normal form.
+    ///
+    /// Unlike [`Self::normal_form()`] this returns `self` unchanged if it is already in normal
+    /// form, and only makes a copy otherwise.
+    #[doc(alias = "g_variant_get_normal_form")]
+    #[must_use]
+    pub fn into_normal_form(self) -> Self {
+        if self.is_normal_form() {
+            self
+        } else {
+            self.normal_form()
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Compares two variants while ignoring the element types of empty containers.
+    ///
+    /// Variants of the same type are compared with [`PartialEq`]. Otherwise both have to be
+    /// containers of the same class, e.g. both arrays, with the same number of children that
+    /// are pairwise structurally equal.
+    ///
+    /// This only diverges from [`PartialEq`] where the types differ just in the element type of
+    /// empty arrays or `Nothing` maybes, at any nesting level. `@as []` and `@ai []` are
+    /// structurally equal, while `[1]` and `[int64 1]` are not.
+    pub fn structurally_eq(&self, other: &Variant) -> bool {
+        if self.type_() == other.type_() {
+            return self == other;
+        }
+
+        if self.classify() != other.classify() || !self.is_container() {
+            return false;
+        }
+
+        let n_children = self.n_children();
+        n_children == other.n_children()
+            && (0..n_children).all(|i| self.child_value(i).structurally_eq(&other.child_value(i)))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Reinterprets the serialized data of the variant as type `ty`.
+    ///
+    /// The data is checked to be in normal form for `ty`, which ensures it has a matching
+    /// structure. This allows retagging values of compatible types that differ only in how
+    /// they were constructed, e.g. an `ay` as `(yy)` if it holds exactly two bytes.
+    ///
+    /// Returns an error if `ty` is not definite or if the data is not valid for `ty`.
+    #[doc(alias = "g_variant_new_from_bytes")]
+    pub fn with_type(&self, ty: &VariantTy) -> Result<Variant, VariantTypeMismatchError> {
+        if self.type_() == ty {
+            return Ok(self.clone());
+        }
+
+        let mismatch = || VariantTypeMismatchError::new(self.type_().to_owned(), ty.to_owned());
+        if !ty.is_definite() {
+            return Err(mismatch());
+        }
+
+        let bytes = self.normal_form().data_as_bytes();
+        let variant = Variant::from_bytes_with_type(&bytes, ty);
+        if variant.is_normal_form() {
+            Ok(variant)
+        } else {
+            Err(mismatch())
+        }
+    }
+
     // rustdoc-stripper-ignore-next
     /// Returns a copy of the variant in the opposite endianness.
     #[doc(alias = "g_variant_byteswap")]
@@ -824,74 +1817,270 @@ impl Variant {
     }
 
     // rustdoc-stripper-ignore-next
-    /// Create an iterator over borrowed strings from a GVariant of type `as` (array of string).
+    /// Create an iterator over the items in the variant together with their index.
     ///
-    /// This will fail if the variant is not an array of with
-    /// the expected child type.
+    /// This is useful to point at the offending child in error messages.
     ///
-    /// A benefit of this API over [`Self::iter()`] is that it
-    /// minimizes allocation, and provides strongly typed access.
+    /// # Panics
     ///
-    /// ```
-    /// # use glib::prelude::*;
-    /// let strs = &["foo", "bar"];
-    /// let strs_variant: glib::Variant = strs.to_variant();
-    /// for s in strs_variant.array_iter_str()? {
-    ///     println!("{}", s);
-    /// }
-    /// # Ok::<(), Box<dyn std::error::Error>>(())
-    /// ```
-    pub fn array_iter_str(&self) -> Result<VariantStrIter, VariantTypeMismatchError> {
-        let child_ty = String::static_variant_type();
-        let actual_ty = self.type_();
-        let expected_ty = child_ty.as_array();
-        if actual_ty != expected_ty {
-            return Err(VariantTypeMismatchError {
-                actual: actual_ty.to_owned(),
-                expected: expected_ty.into_owned(),
-            });
-        }
-
-        Ok(VariantStrIter::new(self))
+    /// Panics if `self` is not a container type.
+    pub fn iter_indexed(&self) -> std::iter::Enumerate<VariantIter> {
+        self.iter().enumerate()
     }
 
     // rustdoc-stripper-ignore-next
-    /// Return whether this Variant is a container type.
-    #[doc(alias = "g_variant_is_container")]
-    pub fn is_container(&self) -> bool {
-        unsafe { from_glib(ffi::g_variant_is_container(self.to_glib_none().0)) }
+    /// Create an iterator over the children of the variant rendered as text.
+    ///
+    /// Each child is printed with its [`Display`](fmt::Display) implementation, i.e. the
+    /// GVariant text format including type annotations, regardless of its type. This is meant
+    /// for human-readable listings in logs. Use [`Self::array_iter_str()`] to read the
+    /// contents of a string array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is not a container type.
+    #[doc(alias = "g_variant_print")]
+    pub fn iter_as_strings(&self) -> impl Iterator<Item = String> {
+        self.iter().map(|child| child.to_string())
     }
 
     // rustdoc-stripper-ignore-next
-    /// Return whether this Variant is in normal form.
-    #[doc(alias = "g_variant_is_normal_form")]
-    pub fn is_normal_form(&self) -> bool {
-        unsafe { from_glib(ffi::g_variant_is_normal_form(self.to_glib_none().0)) }
+    /// Collects all children of a container variant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is not a container type.
+    pub fn children(&self) -> Vec<Variant> {
+        self.iter().collect()
     }
 
     // rustdoc-stripper-ignore-next
-    /// Return whether input string is a valid `VariantClass::ObjectPath`.
-    #[doc(alias = "g_variant_is_object_path")]
-    pub fn is_object_path(string: &str) -> bool {
-        unsafe { from_glib(ffi::g_variant_is_object_path(string.to_glib_none().0)) }
+    /// Collects all children of a container variant.
+    ///
+    /// Returns `None` if `self` is not a container type.
+    pub fn try_children(&self) -> Option<Vec<Variant>> {
+        if !self.is_container() {
+            return None;
+        }
+
+        Some(self.children())
     }
 
     // rustdoc-stripper-ignore-next
-    /// Return whether input string is a valid `VariantClass::Signature`.
-    #[doc(alias = "g_variant_is_signature")]
-    pub fn is_signature(string: &str) -> bool {
-        unsafe { from_glib(ffi::g_variant_is_signature(string.to_glib_none().0)) }
-    }
-}
+    /// Collects the children in `range` of a container variant.
+    ///
+    /// This is useful to page through large arrays without reading every child.
+    /// An empty range returns an empty `Vec`.
+    ///
+    /// # Panics
+    ///
+    /// * if `self` is not a container type.
+    /// * if `range.start > range.end` or `range.end` is larger than the number of children.
+    pub fn child_values_range(&self, range: std::ops::Range<usize>) -> Vec<Variant> {
+        let n_children = self.n_children();
+        assert!(
+            range.start <= range.end && range.end <= n_children,
+            "range {range:?} out of bounds for container with {n_children} children",
+        );
 
-unsafe impl Send for Variant {}
-unsafe impl Sync for Variant {}
+        range
+            .map(|index| unsafe {
+                from_glib_full(ffi::g_variant_get_child_value(self.to_glib_none().0, index))
+            })
+            .collect()
+    }
 
-impl fmt::Debug for Variant {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("Variant")
-            .field("ptr", &ToGlibPtr::<*const _>::to_glib_none(self).0)
-            .field("type", &self.type_())
+    // rustdoc-stripper-ignore-next
+    /// Returns a copy of the container with the child at `index` replaced by `new_child`.
+    ///
+    /// All other children are copied over unchanged. `new_child` must have the same type as
+    /// the child it replaces, which is the element type for arrays and the type at the same
+    /// position for tuples and dictionary entries.
+    ///
+    /// # Panics
+    ///
+    /// * if `self` is not a container type.
+    /// * if given `index` is larger than number of children.
+    pub fn with_child_replaced(
+        &self,
+        index: usize,
+        new_child: &Variant,
+    ) -> Result<Variant, VariantTypeMismatchError> {
+        let old_child = self.child_value(index);
+        if old_child.type_() != new_child.type_() {
+            let segment = if self.type_().is_array() {
+                format!("[{index}]")
+            } else {
+                format!(".{index}")
+            };
+            return Err(VariantTypeMismatchError::new(
+                new_child.type_().to_owned(),
+                old_child.type_().to_owned(),
+            )
+            .with_path_prefix(segment));
+        }
+
+        unsafe {
+            let mut builder = mem::MaybeUninit::uninit();
+            ffi::g_variant_builder_init(builder.as_mut_ptr(), self.type_().to_glib_none().0);
+            let mut builder = builder.assume_init();
+            for (i, child) in self.iter_indexed() {
+                let child = if i == index { new_child } else { &child };
+                ffi::g_variant_builder_add_value(&mut builder, child.to_glib_none().0);
+            }
+            Ok(from_glib_none(ffi::g_variant_builder_end(&mut builder)))
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the total number of values nested inside the variant.
+    ///
+    /// Unlike [`n_children()`](Self::n_children) this counts the children of all container
+    /// levels, so every nested container and every leaf value is counted once. A
+    /// non-container variant has a count of 0.
+    ///
+    /// The variant is walked iteratively, so this is safe to call on deeply nested untrusted
+    /// input.
+    pub fn deep_count(&self) -> usize {
+        let mut count = 0;
+        let mut stack = vec![self.clone()];
+        while let Some(value) = stack.pop() {
+            if value.is_container() {
+                count += value.n_children();
+                stack.extend(value.iter());
+            }
+        }
+
+        count
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Checks that values are nested no deeper than `max_depth` inside the variant.
+    ///
+    /// The variant itself is at depth 0 and each container level adds one, so a
+    /// non-container variant always passes and `[[1]]` needs a `max_depth` of at least 2.
+    ///
+    /// The variant is walked iteratively, so this can be used to reject malicious input
+    /// before handing it to the recursive [`FromVariant`] implementations.
+    pub fn validate_depth(&self, max_depth: usize) -> Result<(), crate::BoolError> {
+        let mut stack = vec![(self.clone(), 0)];
+        while let Some((value, depth)) = stack.pop() {
+            if !value.is_container() || value.n_children() == 0 {
+                continue;
+            }
+            if depth >= max_depth {
+                return Err(bool_error!(
+                    "Variant is nested deeper than the maximum depth of {}",
+                    max_depth
+                ));
+            }
+            stack.extend(value.iter().map(|child| (child, depth + 1)));
+        }
+
+        Ok(())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Create an iterator over borrowed strings from a GVariant of type `as` (array of string).
+    ///
+    /// This will fail if the variant is not an array of with
+    /// the expected child type.
+    ///
+    /// A benefit of this API over [`Self::iter()`] is that it
+    /// minimizes allocation, and provides strongly typed access.
+    ///
+    /// ```
+    /// # use glib::prelude::*;
+    /// let strs = &["foo", "bar"];
+    /// let strs_variant: glib::Variant = strs.to_variant();
+    /// for s in strs_variant.array_iter_str()? {
+    ///     println!("{}", s);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn array_iter_str(&self) -> Result<VariantStrIter, VariantTypeMismatchError> {
+        let child_ty = String::static_variant_type();
+        let actual_ty = self.type_();
+        let expected_ty = child_ty.as_array();
+        if actual_ty != expected_ty {
+            return Err(VariantTypeMismatchError::new(
+                actual_ty.to_owned(),
+                expected_ty.into_owned(),
+            ));
+        }
+
+        Ok(VariantStrIter::new(self))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Return whether this Variant is a container type.
+    #[doc(alias = "g_variant_is_container")]
+    pub fn is_container(&self) -> bool {
+        unsafe { from_glib(ffi::g_variant_is_container(self.to_glib_none().0)) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Feeds the type string and value of this Variant into the given hasher.
+    ///
+    /// Unlike `g_variant_hash()`, which is only defined for basic types, this recurses into
+    /// containers, so equal variants always hash equally.
+    pub fn stable_hash<H: Hasher>(&self, state: &mut H) {
+        self.type_().as_str().hash(state);
+
+        if self.is_container() {
+            state.write_usize(self.n_children());
+            for child in self.iter() {
+                child.stable_hash(state);
+            }
+        } else {
+            unsafe {
+                state.write_u32(ffi::g_variant_hash(
+                    ToGlibPtr::<*const _>::to_glib_none(self).0 as *const _,
+                ))
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Return whether this Variant is in normal form.
+    #[doc(alias = "g_variant_is_normal_form")]
+    pub fn is_normal_form(&self) -> bool {
+        unsafe { from_glib(ffi::g_variant_is_normal_form(self.to_glib_none().0)) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Return whether this Variant is not in normal form.
+    ///
+    /// If this returns `false`, [`Self::data()`] can be used as is instead of paying for the copy
+    /// made by [`Self::normal_form()`].
+    #[inline]
+    pub fn needs_normalization(&self) -> bool {
+        !self.is_normal_form()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Return whether input string is a valid `VariantClass::ObjectPath`.
+    #[doc(alias = "g_variant_is_object_path")]
+    pub fn is_object_path(string: &str) -> bool {
+        unsafe { from_glib(ffi::g_variant_is_object_path(string.to_glib_none().0)) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Return whether input string is a valid `VariantClass::Signature`.
+    #[doc(alias = "g_variant_is_signature")]
+    pub fn is_signature(string: &str) -> bool {
+        unsafe { from_glib(ffi::g_variant_is_signature(string.to_glib_none().0)) }
+    }
+}
+
+unsafe impl Send for Variant {}
+unsafe impl Sync for Variant {}
+
+impl fmt::Debug for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Variant")
+            .field("ptr", &ToGlibPtr::<*const _>::to_glib_none(self).0)
+            .field("type", &self.type_())
             .field("value", &self.to_string())
             .finish()
     }
@@ -911,6 +2100,13 @@ impl str::FromStr for Variant {
     }
 }
 
+// rustdoc-stripper-ignore-next
+/// Compares two variants by type and value.
+///
+/// The comparison doesn't depend on how the variants were constructed or whether their
+/// serialized data is in normal form: `g_variant_equal()` only compares the serialized data
+/// directly if both values are trusted to be in normal form, and compares their printed form
+/// otherwise.
 impl PartialEq for Variant {
     #[doc(alias = "g_variant_equal")]
     fn eq(&self, other: &Self) -> bool {
@@ -925,6 +2121,22 @@ impl PartialEq for Variant {
 
 impl Eq for Variant {}
 
+// rustdoc-stripper-ignore-next
+/// Compares the contents of a string variant (`s`, `o` or `g`) with a string slice.
+///
+/// Variants of any other type never compare equal.
+impl PartialEq<str> for Variant {
+    fn eq(&self, other: &str) -> bool {
+        self.str() == Some(other)
+    }
+}
+
+impl PartialEq<&str> for Variant {
+    fn eq(&self, other: &&str) -> bool {
+        self.str() == Some(*other)
+    }
+}
+
 impl PartialOrd for Variant {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         unsafe {
@@ -951,6 +2163,11 @@ impl PartialOrd for Variant {
 impl Hash for Variant {
     #[doc(alias = "g_variant_hash")]
     fn hash<H: Hasher>(&self, state: &mut H) {
+        // g_variant_hash() is only defined for basic types
+        if self.is_container() {
+            return self.stable_hash(state);
+        }
+
         unsafe {
             state.write_u32(ffi::g_variant_hash(
                 ToGlibPtr::<*const _>::to_glib_none(self).0 as *const _,
@@ -966,6 +2183,19 @@ impl AsRef<Variant> for Variant {
     }
 }
 
+// rustdoc-stripper-ignore-next
+/// Returns the serialized data of the variant, like [`Variant::data()`].
+///
+/// This is the binary GVariant serialization format, not the text representation returned
+/// by [`Variant::print()`]. The data is borrowed from the variant and stays valid as long as
+/// the variant is alive.
+impl AsRef<[u8]> for Variant {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.data()
+    }
+}
+
 // rustdoc-stripper-ignore-next
 /// Converts to `Variant`.
 pub trait ToVariant {
@@ -982,6 +2212,36 @@ pub trait FromVariant: Sized + StaticVariantType {
     ///
     /// Returns `Some` if the variant's type matches `Self`.
     fn from_variant(variant: &Variant) -> Option<Self>;
+
+    // rustdoc-stripper-ignore-next
+    /// Tries to extract a value.
+    ///
+    /// Returns an error describing the mismatching types if the variant's type does not match
+    /// `Self`. Container types override this to report the child that failed to convert.
+    fn try_from_variant(variant: &Variant) -> Result<Self, VariantTypeMismatchError> {
+        Self::from_variant(variant)
+            .ok_or_else(|| VariantTypeMismatchError::for_variant::<Self>(variant))
+    }
+
+    // Converts the children of an array variant for `Vec<Self>`. Fixed-size types override
+    // this to copy the elements out in one go instead of allocating a variant per child.
+    #[doc(hidden)]
+    fn try_vec_from_variant(variant: &Variant) -> Result<Vec<Self>, VariantTypeMismatchError> {
+        try_vec_from_children(variant)
+    }
+}
+
+fn try_vec_from_children<T: FromVariant>(
+    variant: &Variant,
+) -> Result<Vec<T>, VariantTypeMismatchError> {
+    variant
+        .iter_indexed()
+        .map(|(i, child)| {
+            child
+                .try_get()
+                .map_err(|err| err.with_path_prefix(format!("[{i}]")))
+        })
+        .collect()
 }
 
 // rustdoc-stripper-ignore-next
@@ -1048,6 +2308,16 @@ macro_rules! impl_numeric {
                     }
                 }
             }
+
+            fn try_vec_from_variant(
+                variant: &Variant,
+            ) -> Result<Vec<Self>, VariantTypeMismatchError> {
+                // Fall back to the children for the error location
+                variant
+                    .fixed_array::<Self>()
+                    .map(<[Self]>::to_vec)
+                    .or_else(|_| try_vec_from_children(variant))
+            }
         }
     };
 }
@@ -1096,6 +2366,50 @@ impl_numeric!(
     g_variant_get_double
 );
 
+// rustdoc-stripper-ignore-next
+/// `i8` is represented as a `y` (byte), reinterpreting the sign bit.
+///
+/// GVariant has no signed byte type, so e.g. `-1i8` is stored as the byte `0xff` and reading a
+/// byte variant as `i8` reinterprets it the same way.
+impl StaticVariantType for i8 {
+    fn static_variant_type() -> Cow<'static, VariantTy> {
+        Cow::Borrowed(VariantTy::BYTE)
+    }
+}
+
+impl ToVariant for i8 {
+    fn to_variant(&self) -> Variant {
+        unsafe { from_glib_none(ffi::g_variant_new_byte(*self as u8)) }
+    }
+}
+
+impl From<i8> for Variant {
+    #[inline]
+    fn from(v: i8) -> Self {
+        v.to_variant()
+    }
+}
+
+impl FromVariant for i8 {
+    fn from_variant(variant: &Variant) -> Option<Self> {
+        unsafe {
+            if variant.is::<Self>() {
+                Some(ffi::g_variant_get_byte(variant.to_glib_none().0) as i8)
+            } else {
+                None
+            }
+        }
+    }
+
+    fn try_vec_from_variant(variant: &Variant) -> Result<Vec<Self>, VariantTypeMismatchError> {
+        // Fall back to the children for the error location
+        variant
+            .fixed_array::<Self>()
+            .map(<[Self]>::to_vec)
+            .or_else(|_| try_vec_from_children(variant))
+    }
+}
+
 impl StaticVariantType for () {
     fn static_variant_type() -> Cow<'static, VariantTy> {
         Cow::Borrowed(VariantTy::UNIT)
@@ -1298,125 +2612,468 @@ impl From<&std::ffi::OsStr> for Variant {
     }
 }
 
-impl<T: StaticVariantType> StaticVariantType for Option<T> {
+// rustdoc-stripper-ignore-next
+/// [`Bytes`] is represented as a byte array (`ay`).
+///
+/// The conversions in both directions share the underlying memory instead of copying it.
+impl StaticVariantType for Bytes {
     fn static_variant_type() -> Cow<'static, VariantTy> {
-        Cow::Owned(VariantType::new_maybe(&T::static_variant_type()))
+        Cow::Borrowed(VariantTy::BYTE_STRING)
     }
 }
 
-impl<T: StaticVariantType + ToVariant> ToVariant for Option<T> {
+impl ToVariant for Bytes {
     fn to_variant(&self) -> Variant {
-        Variant::from_maybe::<T>(self.as_ref().map(|m| m.to_variant()).as_ref())
+        Variant::from_bytes::<Bytes>(self)
     }
 }
 
-impl<T: StaticVariantType + Into<Variant>> From<Option<T>> for Variant {
+impl From<Bytes> for Variant {
     #[inline]
-    fn from(v: Option<T>) -> Self {
-        Variant::from_maybe::<T>(v.map(|v| v.into()).as_ref())
+    fn from(b: Bytes) -> Self {
+        Variant::from_bytes::<Bytes>(&b)
     }
 }
 
-impl<T: StaticVariantType + FromVariant> FromVariant for Option<T> {
+impl FromVariant for Bytes {
     fn from_variant(variant: &Variant) -> Option<Self> {
-        unsafe {
-            if variant.is::<Self>() {
-                let c_child = ffi::g_variant_get_maybe(variant.to_glib_none().0);
-                if !c_child.is_null() {
-                    let child: Variant = from_glib_full(c_child);
-
-                    Some(T::from_variant(&child))
-                } else {
-                    Some(None)
-                }
-            } else {
-                None
-            }
+        if !variant.is::<Self>() {
+            return None;
         }
+
+        Some(variant.data_as_bytes())
     }
 }
 
-impl<T: StaticVariantType> StaticVariantType for [T] {
+// rustdoc-stripper-ignore-next
+/// `SystemTime` is represented as an `x` (int64) of microseconds since the Unix epoch.
+///
+/// Times before the epoch are stored as negative values. [`ToVariant`] saturates at the bounds
+/// of `i64` for times that are out of range, use `Variant::try_from()` to get an error instead.
+/// Converting from a variant returns `None` if the time can't be represented by `SystemTime` on
+/// the current platform.
+impl StaticVariantType for std::time::SystemTime {
     fn static_variant_type() -> Cow<'static, VariantTy> {
-        T::static_variant_type().as_array()
+        i64::static_variant_type()
     }
 }
 
-impl<T: StaticVariantType + ToVariant> ToVariant for [T] {
-    fn to_variant(&self) -> Variant {
-        unsafe {
-            if self.is_empty() {
-                return from_glib_none(ffi::g_variant_new_array(
-                    T::static_variant_type().to_glib_none().0,
-                    ptr::null(),
-                    0,
-                ));
-            }
-
-            let mut builder = mem::MaybeUninit::uninit();
-            ffi::g_variant_builder_init(builder.as_mut_ptr(), VariantTy::ARRAY.to_glib_none().0);
-            let mut builder = builder.assume_init();
-            for value in self {
-                let value = value.to_variant();
-                ffi::g_variant_builder_add_value(&mut builder, value.to_glib_none().0);
-            }
-            from_glib_none(ffi::g_variant_builder_end(&mut builder))
-        }
+// Returns the value saturated at the bounds of `i64` as error on overflow
+fn system_time_to_micros(t: &std::time::SystemTime) -> Result<i64, i64> {
+    match t.duration_since(std::time::UNIX_EPOCH) {
+        Ok(d) => i64::try_from(d.as_micros()).map_err(|_| i64::MAX),
+        Err(e) => i64::try_from(-(e.duration().as_micros() as i128)).map_err(|_| i64::MIN),
     }
 }
 
-impl<T: StaticVariantType + ToVariant> From<&[T]> for Variant {
-    #[inline]
-    fn from(s: &[T]) -> Self {
-        s.to_variant()
+impl ToVariant for std::time::SystemTime {
+    fn to_variant(&self) -> Variant {
+        system_time_to_micros(self)
+            .unwrap_or_else(|saturated| saturated)
+            .to_variant()
     }
 }
 
-impl<T: FromVariant> FromVariant for Vec<T> {
-    fn from_variant(variant: &Variant) -> Option<Self> {
-        if !variant.is_container() {
-            return None;
-        }
+impl TryFrom<std::time::SystemTime> for Variant {
+    type Error = crate::BoolError;
 
-        let mut vec = Vec::with_capacity(variant.n_children());
+    // rustdoc-stripper-ignore-next
+    /// Converts the time to microseconds since the Unix epoch.
+    ///
+    /// Fails if the number of microseconds doesn't fit into an `i64`.
+    fn try_from(t: std::time::SystemTime) -> Result<Self, Self::Error> {
+        system_time_to_micros(&t)
+            .map(|micros| micros.to_variant())
+            .map_err(|_| {
+                bool_error!("Time is out of range for i64 microseconds since the Unix epoch")
+            })
+    }
+}
 
-        for i in 0..variant.n_children() {
-            match variant.child_value(i).get() {
-                Some(child) => vec.push(child),
-                None => return None,
-            }
+impl FromVariant for std::time::SystemTime {
+    fn from_variant(variant: &Variant) -> Option<Self> {
+        let micros = variant.get::<i64>()?;
+        let d = std::time::Duration::from_micros(micros.unsigned_abs());
+        if micros >= 0 {
+            std::time::UNIX_EPOCH.checked_add(d)
+        } else {
+            std::time::UNIX_EPOCH.checked_sub(d)
         }
+    }
+}
 
-        Some(vec)
+// rustdoc-stripper-ignore-next
+/// `Ipv4Addr` is represented as an `ay` (byte array) of exactly 4 bytes in network byte order.
+impl StaticVariantType for std::net::Ipv4Addr {
+    fn static_variant_type() -> Cow<'static, VariantTy> {
+        Cow::Borrowed(VariantTy::BYTE_STRING)
     }
 }
 
-impl<T: StaticVariantType + ToVariant> ToVariant for Vec<T> {
+impl ToVariant for std::net::Ipv4Addr {
     fn to_variant(&self) -> Variant {
-        self.as_slice().to_variant()
+        Variant::array_from_fixed_array(&self.octets())
     }
 }
 
-impl<T: StaticVariantType + Into<Variant>> From<Vec<T>> for Variant {
-    fn from(v: Vec<T>) -> Self {
-        unsafe {
-            if v.is_empty() {
-                return from_glib_none(ffi::g_variant_new_array(
-                    T::static_variant_type().to_glib_none().0,
-                    ptr::null(),
-                    0,
-                ));
-            }
-
-            let mut builder = mem::MaybeUninit::uninit();
-            ffi::g_variant_builder_init(builder.as_mut_ptr(), VariantTy::ARRAY.to_glib_none().0);
-            let mut builder = builder.assume_init();
-            for value in v {
-                let value = value.into();
-                ffi::g_variant_builder_add_value(&mut builder, value.to_glib_none().0);
-            }
-            from_glib_none(ffi::g_variant_builder_end(&mut builder))
-        }
+impl From<std::net::Ipv4Addr> for Variant {
+    #[inline]
+    fn from(addr: std::net::Ipv4Addr) -> Self {
+        addr.to_variant()
+    }
+}
+
+impl FromVariant for std::net::Ipv4Addr {
+    fn from_variant(variant: &Variant) -> Option<Self> {
+        let octets = <[u8; 4]>::try_from(variant.fixed_array::<u8>().ok()?).ok()?;
+        Some(Self::from(octets))
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// `Ipv6Addr` is represented as an `ay` (byte array) of exactly 16 bytes in network byte order.
+impl StaticVariantType for std::net::Ipv6Addr {
+    fn static_variant_type() -> Cow<'static, VariantTy> {
+        Cow::Borrowed(VariantTy::BYTE_STRING)
+    }
+}
+
+impl ToVariant for std::net::Ipv6Addr {
+    fn to_variant(&self) -> Variant {
+        Variant::array_from_fixed_array(&self.octets())
+    }
+}
+
+impl From<std::net::Ipv6Addr> for Variant {
+    #[inline]
+    fn from(addr: std::net::Ipv6Addr) -> Self {
+        addr.to_variant()
+    }
+}
+
+impl FromVariant for std::net::Ipv6Addr {
+    fn from_variant(variant: &Variant) -> Option<Self> {
+        let octets = <[u8; 16]>::try_from(variant.fixed_array::<u8>().ok()?).ok()?;
+        Some(Self::from(octets))
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// `IpAddr` is represented as a `(bay)` tuple.
+///
+/// The boolean is `false` for IPv4 and `true` for IPv6 addresses, and the byte array holds the
+/// address as for [`Ipv4Addr`](std::net::Ipv4Addr) and [`Ipv6Addr`](std::net::Ipv6Addr). Reading
+/// a variant whose byte array length doesn't match the boolean returns `None`.
+impl StaticVariantType for std::net::IpAddr {
+    fn static_variant_type() -> Cow<'static, VariantTy> {
+        <(bool, std::net::Ipv6Addr)>::static_variant_type()
+    }
+}
+
+impl ToVariant for std::net::IpAddr {
+    fn to_variant(&self) -> Variant {
+        match self {
+            Self::V4(addr) => (false, addr).to_variant(),
+            Self::V6(addr) => (true, addr).to_variant(),
+        }
+    }
+}
+
+impl From<std::net::IpAddr> for Variant {
+    #[inline]
+    fn from(addr: std::net::IpAddr) -> Self {
+        addr.to_variant()
+    }
+}
+
+impl FromVariant for std::net::IpAddr {
+    fn from_variant(variant: &Variant) -> Option<Self> {
+        if !variant.is::<Self>() {
+            return None;
+        }
+
+        let addr = variant.child_value(1);
+        if variant.child_value(0).get::<bool>()? {
+            addr.get::<std::net::Ipv6Addr>().map(Self::V6)
+        } else {
+            addr.get::<std::net::Ipv4Addr>().map(Self::V4)
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// `SocketAddr` is represented as a `((bay)q)` tuple of the [`IpAddr`](std::net::IpAddr) and
+/// the port.
+///
+/// The flow information and scope ID of IPv6 socket addresses are not preserved.
+impl StaticVariantType for std::net::SocketAddr {
+    fn static_variant_type() -> Cow<'static, VariantTy> {
+        <(std::net::IpAddr, u16)>::static_variant_type()
+    }
+}
+
+impl ToVariant for std::net::SocketAddr {
+    fn to_variant(&self) -> Variant {
+        (self.ip(), self.port()).to_variant()
+    }
+}
+
+impl From<std::net::SocketAddr> for Variant {
+    #[inline]
+    fn from(addr: std::net::SocketAddr) -> Self {
+        addr.to_variant()
+    }
+}
+
+impl FromVariant for std::net::SocketAddr {
+    fn from_variant(variant: &Variant) -> Option<Self> {
+        let (ip, port) = variant.get::<(std::net::IpAddr, u16)>()?;
+        Some(Self::new(ip, port))
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// `Range` is represented as a `(start, end)` tuple.
+///
+/// `Range` and `RangeInclusive` share this shape, so which kind of range a variant was
+/// created from can't be told from the variant itself.
+impl<T: StaticVariantType> StaticVariantType for std::ops::Range<T> {
+    fn static_variant_type() -> Cow<'static, VariantTy> {
+        <(T, T)>::static_variant_type()
+    }
+}
+
+impl<T: StaticVariantType + ToVariant> ToVariant for std::ops::Range<T> {
+    fn to_variant(&self) -> Variant {
+        (&self.start, &self.end).to_variant()
+    }
+}
+
+impl<T: StaticVariantType + ToVariant> From<std::ops::Range<T>> for Variant {
+    #[inline]
+    fn from(r: std::ops::Range<T>) -> Self {
+        r.to_variant()
+    }
+}
+
+impl<T: StaticVariantType + FromVariant> FromVariant for std::ops::Range<T> {
+    fn from_variant(variant: &Variant) -> Option<Self> {
+        if !variant.is::<Self>() {
+            return None;
+        }
+
+        let (start, end) = variant.get::<(T, T)>()?;
+        Some(start..end)
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// `RangeInclusive` is represented as a `(start, end)` tuple.
+///
+/// `Range` and `RangeInclusive` share this shape, so which kind of range a variant was
+/// created from can't be told from the variant itself.
+impl<T: StaticVariantType> StaticVariantType for std::ops::RangeInclusive<T> {
+    fn static_variant_type() -> Cow<'static, VariantTy> {
+        <(T, T)>::static_variant_type()
+    }
+}
+
+impl<T: StaticVariantType + ToVariant> ToVariant for std::ops::RangeInclusive<T> {
+    fn to_variant(&self) -> Variant {
+        (self.start(), self.end()).to_variant()
+    }
+}
+
+impl<T: StaticVariantType + ToVariant> From<std::ops::RangeInclusive<T>> for Variant {
+    #[inline]
+    fn from(r: std::ops::RangeInclusive<T>) -> Self {
+        r.to_variant()
+    }
+}
+
+impl<T: StaticVariantType + FromVariant> FromVariant for std::ops::RangeInclusive<T> {
+    fn from_variant(variant: &Variant) -> Option<Self> {
+        if !variant.is::<Self>() {
+            return None;
+        }
+
+        let (start, end) = variant.get::<(T, T)>()?;
+        Some(start..=end)
+    }
+}
+
+impl<T: StaticVariantType> StaticVariantType for Option<T> {
+    fn static_variant_type() -> Cow<'static, VariantTy> {
+        Cow::Owned(VariantType::new_maybe(&T::static_variant_type()))
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Converts into a maybe variant.
+///
+/// Through the implementations for references this also covers borrowed values like the
+/// `Option<&T>` returned by `HashMap::get()`, which convert to the same variant as the owned
+/// `Option<T>`.
+impl<T: StaticVariantType + ToVariant> ToVariant for Option<T> {
+    fn to_variant(&self) -> Variant {
+        Variant::from_maybe::<T>(self.as_ref().map(|m| m.to_variant()).as_ref())
+    }
+}
+
+impl<T: StaticVariantType + Into<Variant>> From<Option<T>> for Variant {
+    #[inline]
+    fn from(v: Option<T>) -> Self {
+        Variant::from_maybe::<T>(v.map(|v| v.into()).as_ref())
+    }
+}
+
+impl<T: StaticVariantType + FromVariant> FromVariant for Option<T> {
+    fn from_variant(variant: &Variant) -> Option<Self> {
+        if !variant.is::<Self>() {
+            return None;
+        }
+
+        match variant.maybe_value()? {
+            Some(child) => Some(T::from_variant(&child)),
+            None => Some(None),
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// `Result<T, E>` is represented as a tuple of two maybes `(mTmE)`.
+///
+/// `Ok` values have the first and `Err` values the second element set, so both share the same
+/// type and can be stored together in an array. Tuples where neither or both elements are set are
+/// rejected when converting from a variant.
+impl<T: StaticVariantType, E: StaticVariantType> StaticVariantType for Result<T, E> {
+    fn static_variant_type() -> Cow<'static, VariantTy> {
+        <(Option<T>, Option<E>)>::static_variant_type()
+    }
+}
+
+impl<T, E> ToVariant for Result<T, E>
+where
+    T: StaticVariantType + ToVariant,
+    E: StaticVariantType + ToVariant,
+{
+    fn to_variant(&self) -> Variant {
+        match self {
+            Ok(v) => (Some(v), None::<&E>).to_variant(),
+            Err(e) => (None::<&T>, Some(e)).to_variant(),
+        }
+    }
+}
+
+impl<T, E> From<Result<T, E>> for Variant
+where
+    T: StaticVariantType + Into<Variant>,
+    E: StaticVariantType + Into<Variant>,
+{
+    #[inline]
+    fn from(r: Result<T, E>) -> Self {
+        match r {
+            Ok(v) => (Some(v), None::<E>).into(),
+            Err(e) => (None::<T>, Some(e)).into(),
+        }
+    }
+}
+
+impl<T, E> FromVariant for Result<T, E>
+where
+    T: StaticVariantType + FromVariant,
+    E: StaticVariantType + FromVariant,
+{
+    fn from_variant(variant: &Variant) -> Option<Self> {
+        match variant.get::<(Option<T>, Option<E>)>()? {
+            (Some(v), None) => Some(Ok(v)),
+            (None, Some(e)) => Some(Err(e)),
+            _ => None,
+        }
+    }
+}
+
+impl<T: StaticVariantType> StaticVariantType for [T] {
+    fn static_variant_type() -> Cow<'static, VariantTy> {
+        T::static_variant_type().as_array()
+    }
+}
+
+impl<T: StaticVariantType + ToVariant> ToVariant for [T] {
+    fn to_variant(&self) -> Variant {
+        unsafe {
+            if self.is_empty() {
+                return Variant::empty_array(&T::static_variant_type());
+            }
+
+            let mut builder = mem::MaybeUninit::uninit();
+            ffi::g_variant_builder_init(builder.as_mut_ptr(), VariantTy::ARRAY.to_glib_none().0);
+            let mut builder = builder.assume_init();
+            for value in self {
+                let value = value.to_variant();
+                ffi::g_variant_builder_add_value(&mut builder, value.to_glib_none().0);
+            }
+            from_glib_none(ffi::g_variant_builder_end(&mut builder))
+        }
+    }
+}
+
+impl<T: StaticVariantType + ToVariant> From<&[T]> for Variant {
+    #[inline]
+    fn from(s: &[T]) -> Self {
+        s.to_variant()
+    }
+}
+
+impl<T: StaticVariantType, const N: usize> StaticVariantType for [T; N] {
+    fn static_variant_type() -> Cow<'static, VariantTy> {
+        <[T]>::static_variant_type()
+    }
+}
+
+impl<T: StaticVariantType + ToVariant, const N: usize> ToVariant for [T; N] {
+    #[inline]
+    fn to_variant(&self) -> Variant {
+        self.as_slice().to_variant()
+    }
+}
+
+impl<T: FromVariant> FromVariant for Vec<T> {
+    fn from_variant(variant: &Variant) -> Option<Self> {
+        Self::try_from_variant(variant).ok()
+    }
+
+    fn try_from_variant(variant: &Variant) -> Result<Self, VariantTypeMismatchError> {
+        if !variant.type_().is_array() {
+            return Err(VariantTypeMismatchError::for_variant::<Self>(variant));
+        }
+
+        T::try_vec_from_variant(variant)
+    }
+}
+
+impl<T: StaticVariantType + ToVariant> ToVariant for Vec<T> {
+    fn to_variant(&self) -> Variant {
+        self.as_slice().to_variant()
+    }
+}
+
+impl<T: StaticVariantType + Into<Variant>> From<Vec<T>> for Variant {
+    fn from(v: Vec<T>) -> Self {
+        unsafe {
+            if v.is_empty() {
+                return Variant::empty_array(&T::static_variant_type());
+            }
+
+            let mut builder = mem::MaybeUninit::uninit();
+            ffi::g_variant_builder_init(builder.as_mut_ptr(), VariantTy::ARRAY.to_glib_none().0);
+            let mut builder = builder.assume_init();
+            for value in v {
+                let value = value.into();
+                ffi::g_variant_builder_add_value(&mut builder, value.to_glib_none().0);
+            }
+            from_glib_none(ffi::g_variant_builder_end(&mut builder))
+        }
     }
 }
 
@@ -1426,6 +3083,34 @@ impl<T: StaticVariantType> StaticVariantType for Vec<T> {
     }
 }
 
+// Converts the dictionary entry at `index`, prefixing errors with the location of the entry.
+fn try_map_entry<K: FromVariant, V: FromVariant>(
+    index: usize,
+    entry: &Variant,
+) -> Result<(K, V), VariantTypeMismatchError> {
+    if !entry.type_().is_subtype_of(VariantTy::DICT_ENTRY) {
+        return Err(
+            VariantTypeMismatchError::for_variant::<DictEntry<K, V>>(entry)
+                .with_path_prefix(format!("[{index}]")),
+        );
+    }
+
+    let key = entry.child_value(0);
+    let k = key.try_get().map_err(|err| {
+        err.with_path_prefix(".0")
+            .with_path_prefix(format!("[{index}]"))
+    })?;
+    let v = entry.child_value(1).try_get().map_err(|err| {
+        let key = match key.str() {
+            Some(key) => key.to_owned(),
+            None => key.print(false).into(),
+        };
+        err.with_path_prefix(format!("{{{key}}}"))
+    })?;
+
+    Ok((k, v))
+}
+
 impl<K, V, H> FromVariant for HashMap<K, V, H>
 where
     K: FromVariant + Eq + Hash,
@@ -1433,7 +3118,8 @@ where
     H: BuildHasher + Default,
 {
     fn from_variant(variant: &Variant) -> Option<Self> {
-        if !variant.is_container() {
+        // Only dictionaries are guaranteed to have dict entry children
+        if !variant.type_().is_subtype_of(VariantTy::DICTIONARY) {
             return None;
         }
 
@@ -1449,6 +3135,17 @@ where
 
         Some(map)
     }
+
+    fn try_from_variant(variant: &Variant) -> Result<Self, VariantTypeMismatchError> {
+        if !variant.type_().is_subtype_of(VariantTy::DICTIONARY) {
+            return Err(VariantTypeMismatchError::for_variant::<Self>(variant));
+        }
+
+        variant
+            .iter_indexed()
+            .map(|(i, entry)| try_map_entry(i, &entry))
+            .collect()
+    }
 }
 
 impl<K, V> FromVariant for BTreeMap<K, V>
@@ -1457,7 +3154,8 @@ where
     V: FromVariant,
 {
     fn from_variant(variant: &Variant) -> Option<Self> {
-        if !variant.is_container() {
+        // Only dictionaries are guaranteed to have dict entry children
+        if !variant.type_().is_subtype_of(VariantTy::DICTIONARY) {
             return None;
         }
 
@@ -1473,6 +3171,17 @@ where
 
         Some(map)
     }
+
+    fn try_from_variant(variant: &Variant) -> Result<Self, VariantTypeMismatchError> {
+        if !variant.type_().is_subtype_of(VariantTy::DICTIONARY) {
+            return Err(VariantTypeMismatchError::for_variant::<Self>(variant));
+        }
+
+        variant
+            .iter_indexed()
+            .map(|(i, entry)| try_map_entry(i, &entry))
+            .collect()
+    }
 }
 
 impl<K, V> ToVariant for HashMap<K, V>
@@ -1483,11 +3192,7 @@ where
     fn to_variant(&self) -> Variant {
         unsafe {
             if self.is_empty() {
-                return from_glib_none(ffi::g_variant_new_array(
-                    DictEntry::<K, V>::static_variant_type().to_glib_none().0,
-                    ptr::null(),
-                    0,
-                ));
+                return Variant::empty_array(&DictEntry::<K, V>::static_variant_type());
             }
 
             let mut builder = mem::MaybeUninit::uninit();
@@ -1510,11 +3215,7 @@ where
     fn from(m: HashMap<K, V>) -> Self {
         unsafe {
             if m.is_empty() {
-                return from_glib_none(ffi::g_variant_new_array(
-                    DictEntry::<K, V>::static_variant_type().to_glib_none().0,
-                    ptr::null(),
-                    0,
-                ));
+                return Variant::empty_array(&DictEntry::<K, V>::static_variant_type());
             }
 
             let mut builder = mem::MaybeUninit::uninit();
@@ -1537,11 +3238,7 @@ where
     fn to_variant(&self) -> Variant {
         unsafe {
             if self.is_empty() {
-                return from_glib_none(ffi::g_variant_new_array(
-                    DictEntry::<K, V>::static_variant_type().to_glib_none().0,
-                    ptr::null(),
-                    0,
-                ));
+                return Variant::empty_array(&DictEntry::<K, V>::static_variant_type());
             }
 
             let mut builder = mem::MaybeUninit::uninit();
@@ -1564,11 +3261,7 @@ where
     fn from(m: BTreeMap<K, V>) -> Self {
         unsafe {
             if m.is_empty() {
-                return from_glib_none(ffi::g_variant_new_array(
-                    DictEntry::<K, V>::static_variant_type().to_glib_none().0,
-                    ptr::null(),
-                    0,
-                ));
+                return Variant::empty_array(&DictEntry::<K, V>::static_variant_type());
             }
 
             let mut builder = mem::MaybeUninit::uninit();
@@ -1601,6 +3294,20 @@ where
 /// assert_eq!(dict.n_children(), 2);
 /// assert_eq!(dict.type_().as_str(), "a{su}");
 /// ```
+///
+/// Collecting `(K, V)` tuples directly creates an array of tuples instead. Map them through
+/// [`DictEntry::from`] to create a dictionary:
+///
+/// ```
+///# use glib::prelude::*; // or `use gtk::prelude::*;`
+/// use glib::variant::{Variant, DictEntry};
+///
+/// let pairs = [("uuid", 1000u32), ("guid", 1001u32)];
+/// assert_eq!(pairs.into_iter().collect::<Variant>().type_().as_str(), "a(su)");
+///
+/// let dict = pairs.into_iter().map(DictEntry::from).collect::<Variant>();
+/// assert_eq!(dict.type_().as_str(), "a{su}");
+/// ```
 pub struct DictEntry<K, V> {
     key: K,
     value: V,
@@ -1622,6 +3329,32 @@ where
     pub fn value(&self) -> &V {
         &self.value
     }
+
+    pub fn into_parts(self) -> (K, V) {
+        (self.key, self.value)
+    }
+}
+
+impl<K, V> From<(K, V)> for DictEntry<K, V>
+where
+    K: StaticVariantType,
+    V: StaticVariantType,
+{
+    #[inline]
+    fn from((key, value): (K, V)) -> Self {
+        Self::new(key, value)
+    }
+}
+
+impl<K, V> From<DictEntry<K, V>> for (K, V)
+where
+    K: StaticVariantType,
+    V: StaticVariantType,
+{
+    #[inline]
+    fn from(e: DictEntry<K, V>) -> Self {
+        e.into_parts()
+    }
 }
 
 impl<K, V> FromVariant for DictEntry<K, V>
@@ -1639,6 +3372,23 @@ where
 
         Some(Self { key, value })
     }
+
+    fn try_from_variant(variant: &Variant) -> Result<Self, VariantTypeMismatchError> {
+        if !variant.type_().is_subtype_of(VariantTy::DICT_ENTRY) {
+            return Err(VariantTypeMismatchError::for_variant::<Self>(variant));
+        }
+
+        let key = variant
+            .child_value(0)
+            .try_get()
+            .map_err(|err| err.with_path_prefix(".0"))?;
+        let value = variant
+            .child_value(1)
+            .try_get()
+            .map_err(|err| err.with_path_prefix(".1"))?;
+
+        Ok(Self { key, value })
+    }
 }
 
 impl<K, V> ToVariant for DictEntry<K, V>
@@ -1723,6 +3473,8 @@ where
     }
 }
 
+// Tuples of references like `(&A, &B)` are covered through the impls for `&T`, so borrowed
+// fields don't need to be cloned to build a tuple variant.
 macro_rules! tuple_impls {
     ($($len:expr => ($($n:tt $name:ident)+))+) => {
         $(
@@ -1757,6 +3509,23 @@ macro_rules! tuple_impls {
                         )+
                     ))
                 }
+
+                fn try_from_variant(variant: &Variant) -> Result<Self, VariantTypeMismatchError> {
+                    if !variant.type_().is_subtype_of(VariantTy::TUPLE) {
+                        return Err(VariantTypeMismatchError::for_variant::<Self>(variant));
+                    }
+
+                    Ok((
+                        $(
+                            match variant.try_child_value($n) {
+                                Some(field) => field
+                                    .try_get::<$name>()
+                                    .map_err(|err| err.with_path_prefix(concat!(".", $n)))?,
+                                None => return Err(VariantTypeMismatchError::for_variant::<Self>(variant)),
+                            },
+                        )+
+                    ))
+                }
             }
 
             impl<$($name),+> ToVariant for ($($name,)+)
@@ -1821,14 +3590,136 @@ tuple_impls! {
     16 => (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11 12 T12 13 T13 14 T14 15 T15)
 }
 
+// rustdoc-stripper-ignore-next
+/// Creates a tuple `Variant` from heterogeneous values.
+///
+/// Each argument is converted with [`ToVariant::to_variant`] and the results are passed to
+/// [`Variant::tuple_from_iter`].
+///
+/// ```
+/// let v = glib::variant_tuple!("x", 1u8, 2u32);
+/// assert_eq!(v.type_().as_str(), "(syu)");
+/// ```
+#[macro_export]
+macro_rules! variant_tuple {
+    () => {
+        $crate::variant::ToVariant::to_variant(&())
+    };
+    ($($value:expr),+ $(,)?) => {
+        $crate::Variant::tuple_from_iter([
+            $($crate::variant::ToVariant::to_variant(&$value)),+
+        ])
+    };
+}
+
 impl<T: Into<Variant> + StaticVariantType> FromIterator<T> for Variant {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         Variant::array_from_iter::<T>(iter.into_iter().map(|v| v.into()))
     }
 }
 
+// rustdoc-stripper-ignore-next
+/// A dynamically typed value tree that can be turned into a [`Variant`].
+///
+/// This is useful for building variants from data whose shape is only known at runtime,
+/// e.g. in scripting scenarios. See [`Variant::from_dynamic()`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    // rustdoc-stripper-ignore-next
+    /// A boolean (`b`).
+    Bool(bool),
+    // rustdoc-stripper-ignore-next
+    /// A signed 64 bit integer (`x`).
+    I64(i64),
+    // rustdoc-stripper-ignore-next
+    /// An unsigned 64 bit integer (`t`).
+    U64(u64),
+    // rustdoc-stripper-ignore-next
+    /// A double (`d`).
+    F64(f64),
+    // rustdoc-stripper-ignore-next
+    /// A string (`s`).
+    Str(String),
+    // rustdoc-stripper-ignore-next
+    /// An array. All elements must convert to the same type.
+    Array(Vec<Value>),
+    // rustdoc-stripper-ignore-next
+    /// A dictionary. Keys must be basic values and all entries must convert to the same types.
+    Dict(Vec<(Value, Value)>),
+    // rustdoc-stripper-ignore-next
+    /// A tuple.
+    Tuple(Vec<Value>),
+}
+
+// rustdoc-stripper-ignore-next
+/// Asserts that the variant type of `T` has the type string `expected`.
+///
+/// This is meant for unit tests that lock down the wire format of a type, so that e.g.
+/// accidentally reordered fields are caught.
+///
+/// ```
+/// glib::variant::assert_type_string::<(String, u32, Vec<u8>)>("(suay)");
+/// ```
+///
+/// # Panics
+///
+/// Panics with both type strings if they don't match.
+#[track_caller]
+pub fn assert_type_string<T: StaticVariantType + ?Sized>(expected: &str) {
+    let actual = T::static_variant_type();
+    assert!(
+        actual.as_str() == expected,
+        "Variant type of `{}` is '{}', expected '{}'",
+        std::any::type_name::<T>(),
+        actual,
+        expected,
+    );
+}
+
+// rustdoc-stripper-ignore-next
+/// Returns the serialized size of an array of `n_elements` values of type `ty`.
+///
+/// This only works for fixed-size element types, i.e. numbers, booleans and tuples or
+/// dictionary entries made up of those. `None` is returned for variable-size or indefinite
+/// types, for which the size depends on the data, and on overflow.
+///
+/// ```
+/// # use glib::{prelude::*, variant::estimate_serialized_size, VariantTy};
+/// assert_eq!(estimate_serialized_size(VariantTy::UINT32, 10), Some(40));
+/// assert_eq!(estimate_serialized_size(VariantTy::STRING, 10), None);
+/// assert_eq!([1u32; 10].to_variant().size(), 40);
+/// ```
+pub fn estimate_serialized_size(ty: &VariantTy, n_elements: usize) -> Option<usize> {
+    let (size, _) = fixed_size_and_alignment(ty)?;
+    size.checked_mul(n_elements)
+}
+
+// Implements the fixed size rules of the GVariant serialization format. Returns the size and
+// the alignment of `ty`, or `None` if it is not fixed-size.
+fn fixed_size_and_alignment(ty: &VariantTy) -> Option<(usize, usize)> {
+    match ty.as_str() {
+        "b" | "y" => Some((1, 1)),
+        "n" | "q" => Some((2, 2)),
+        "i" | "u" | "h" => Some((4, 4)),
+        "x" | "t" | "d" => Some((8, 8)),
+        "()" => Some((1, 1)),
+        _ if ty.is_definite() && (ty.is_tuple() || ty.is_dict_entry()) => {
+            let mut offset = 0usize;
+            let mut alignment = 1usize;
+            for member in ty.tuple_types() {
+                let (size, align) = fixed_size_and_alignment(member)?;
+                offset = offset.next_multiple_of(align) + size;
+                alignment = alignment.max(align);
+            }
+            Some((offset.next_multiple_of(alignment), alignment))
+        }
+        _ => None,
+    }
+}
+
 /// Trait for fixed size variant types.
 pub unsafe trait FixedSizeVariantType: StaticVariantType + Sized + Copy {}
+unsafe impl FixedSizeVariantType for i8 {}
 unsafe impl FixedSizeVariantType for u8 {}
 unsafe impl FixedSizeVariantType for i16 {}
 unsafe impl FixedSizeVariantType for u16 {}
@@ -1950,7 +3841,7 @@ impl<A: AsRef<[T]>, T: FixedSizeVariantType> From<FixedSizeVariantArray<A, T>> f
             }
 
             from_glib_none(ffi::g_variant_new_from_data(
-                T::static_variant_type().to_glib_none().0,
+                <[T]>::static_variant_type().to_glib_none().0,
                 data_ptr as ffi::gconstpointer,
                 len,
                 false.into_glib(),
@@ -2008,6 +3899,48 @@ impl FromVariant for Handle {
     }
 }
 
+/// A wrapper type for flags that are stored as their raw `u32` value (`u`).
+///
+/// This allows bitflag types, which are usually serialized as plain integers e.g. on D-Bus,
+/// to round-trip through a variant with a typed wrapper. `T` has to convert to and from
+/// `u32`.
+///
+/// The value is reconstructed from the raw integer with `T::from()` without checking that
+/// only known bits are set. This is up to the `From<u32>` implementation of `T`.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Flags<T>(pub T);
+
+impl<T> From<T> for Flags<T> {
+    fn from(v: T) -> Self {
+        Flags(v)
+    }
+}
+
+impl<T> StaticVariantType for Flags<T> {
+    fn static_variant_type() -> Cow<'static, VariantTy> {
+        Cow::Borrowed(VariantTy::UINT32)
+    }
+}
+
+impl<T: Copy + Into<u32>> ToVariant for Flags<T> {
+    fn to_variant(&self) -> Variant {
+        self.0.into().to_variant()
+    }
+}
+
+impl<T: Into<u32>> From<Flags<T>> for Variant {
+    #[inline]
+    fn from(f: Flags<T>) -> Self {
+        f.0.into().to_variant()
+    }
+}
+
+impl<T: From<u32>> FromVariant for Flags<T> {
+    fn from_variant(variant: &Variant) -> Option<Self> {
+        variant.get::<u32>().map(|v| Flags(T::from(v)))
+    }
+}
+
 /// A wrapper type around `Variant` object paths.
 ///
 /// Values of these type are guaranteed to be valid object paths.
@@ -2214,6 +4147,13 @@ mod tests {
     signed!(test_i32, i32);
     signed!(test_i64, i64);
 
+    #[test]
+    fn test_type_string() {
+        let v = ("test", 1u8, vec![2u32]).to_variant();
+        assert_eq!(v.type_string(), "(syau)");
+        assert_eq!(v.type_string(), v.type_().as_str());
+    }
+
     #[test]
     fn test_str() {
         let s = "this is a test";
@@ -2222,6 +4162,19 @@ mod tests {
         assert_eq!(42u32.to_variant().str(), None);
     }
 
+    #[test]
+    fn test_str_exact() {
+        assert_eq!("/foo".to_variant().str_exact(), Some("/foo"));
+        let path = ObjectPath::try_from("/foo").unwrap().to_variant();
+        assert_eq!(path.str(), Some("/foo"));
+        assert_eq!(path.str_exact(), None);
+        assert_eq!(path.get::<ObjectPath>().unwrap().as_str(), "/foo");
+        let sig = Signature::try_from("as").unwrap().to_variant();
+        assert_eq!(sig.str_exact(), None);
+        assert_eq!(sig.get::<Signature>().unwrap().as_str(), "as");
+        assert_eq!(42u32.to_variant().str_exact(), None);
+    }
+
     #[test]
     fn test_fixed_array() {
         let b = b"this is a test";
@@ -2268,6 +4221,27 @@ mod tests {
         assert_eq!(v.get::<FixedSizeVariantArray<Vec<i32>, i32>>().unwrap(), b);
     }
 
+    #[test]
+    fn test_from_fixed_vec() {
+        let b = vec![1.0f64, 2.0f64, 3.0f64];
+        let bytes = b.iter().flat_map(|d| d.to_ne_bytes()).collect::<Vec<u8>>();
+        let v = Variant::from_fixed_vec(b.clone());
+        assert_eq!(v.type_().as_str(), "ad");
+        assert_eq!(v.data(), bytes);
+        #[allow(clippy::float_cmp)]
+        {
+            assert_eq!(v.fixed_array::<f64>().unwrap(), b);
+        }
+
+        let v = Variant::from(FixedSizeVariantArray::<Vec<u32>, u32>::from(vec![1, 2, 3]));
+        assert_eq!(v.type_().as_str(), "au");
+        assert_eq!(v.fixed_array::<u32>().unwrap(), [1, 2, 3]);
+
+        let v = Variant::from_fixed_vec(Vec::<i16>::new());
+        assert_eq!(v.type_().as_str(), "an");
+        assert!(v.fixed_array::<i16>().unwrap().is_empty());
+    }
+
     #[test]
     fn test_string() {
         let s = String::from("this is a test");
@@ -2285,6 +4259,30 @@ mod tests {
         assert_ne!(v1, v3);
     }
 
+    #[test]
+    fn test_eq_non_normal_form() {
+        let a = true.to_variant();
+        let b = Variant::from_data::<bool, _>([2u8]);
+        assert!(!b.is_normal_form());
+        assert_eq!(a, b);
+        assert_ne!(b, false.to_variant());
+        assert_ne!(a, 1u8.to_variant());
+    }
+
+    #[test]
+    fn test_into_normal_form() {
+        let a = (1u32, "foo").to_variant();
+        let ptr = a.as_ptr();
+        let a = a.into_normal_form();
+        assert_eq!(a.as_ptr(), ptr);
+
+        let b = Variant::from_data::<bool, _>([2u8]);
+        assert!(!b.is_normal_form());
+        let b = b.into_normal_form();
+        assert!(b.is_normal_form());
+        assert_eq!(b, true.to_variant());
+    }
+
     #[test]
     fn test_hash() {
         let v1 = "this is a test".to_variant();
@@ -2301,6 +4299,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_stable_hash() {
+        use std::hash::{BuildHasher, RandomState};
+
+        let s = RandomState::new();
+        let hash = |v: &Variant| {
+            let mut h = s.build_hasher();
+            v.stable_hash(&mut h);
+            h.finish()
+        };
+
+        let v1 = ("foo", 1u32, vec![1u8, 2u8]).to_variant();
+        let v2 = ("foo", 1u32, vec![1u8, 2u8]).to_variant();
+        let v3 = ("foo", 1u32, vec![2u8, 1u8]).to_variant();
+        assert_eq!(hash(&v1), hash(&v2));
+        assert_ne!(hash(&v1), hash(&v3));
+        assert_ne!(hash(&1u32.to_variant()), hash(&1i32.to_variant()));
+
+        let mut set = HashSet::new();
+        set.insert(v1);
+        assert!(set.contains(&v2));
+        assert!(!set.contains(&v3));
+    }
+
     #[test]
     fn test_array() {
         assert_eq!(<Vec<&str>>::static_variant_type().as_str(), "as");
@@ -2315,6 +4337,14 @@ mod tests {
         assert!(o.array_iter_str().is_err());
     }
 
+    #[test]
+    fn test_empty_array() {
+        let ty = VariantTy::new("(sa{sv})").unwrap();
+        let a = Variant::empty_array(ty);
+        assert_eq!(a.type_().as_str(), "a(sa{sv})");
+        assert_eq!(a.n_children(), 0);
+    }
+
     #[test]
     fn test_array_from_iter() {
         let a = Variant::array_from_iter::<String>(
@@ -2354,6 +4384,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tuple_ref() {
+        let name = String::from("test");
+        let values = vec![1u32, 2, 3];
+        let v = (&name, &values, &1u8).to_variant();
+        assert_eq!(v.type_().as_str(), "(sauy)");
+        assert_eq!(v, (name.clone(), values.clone(), 1u8).to_variant());
+        assert_eq!(
+            <(&String, &Vec<u32>)>::static_variant_type(),
+            <(String, Vec<u32>)>::static_variant_type()
+        );
+    }
+
     #[test]
     fn test_tuple_from_iter() {
         let a = Variant::tuple_from_iter(["foo".to_variant(), 1u8.to_variant(), 2i32.to_variant()]);
@@ -2365,6 +4408,14 @@ mod tests {
         assert_eq!(a.try_child_get::<i32>(2), Ok(Some(2i32)));
     }
 
+    #[test]
+    fn test_variant_tuple() {
+        let a = crate::variant_tuple!("foo", 1u8, vec![2i32]);
+        assert_eq!(a.type_().as_str(), "(syai)");
+        assert_eq!(a, ("foo", 1u8, vec![2i32]).to_variant());
+        assert_eq!(crate::variant_tuple!().type_().as_str(), "()");
+    }
+
     #[test]
     fn test_empty() {
         assert_eq!(<()>::static_variant_type().as_str(), "()");
@@ -2386,6 +4437,146 @@ mod tests {
         assert!(m2.as_maybe().is_none());
     }
 
+    #[test]
+    fn test_maybe_ref() {
+        let map = HashMap::from([("a", String::from("foo"))]);
+        let some: Option<&String> = map.get("a");
+        assert_eq!(some.to_variant(), Some("foo").to_variant());
+
+        let none: Option<&String> = map.get("b");
+        let v = none.to_variant();
+        assert_eq!(v.type_().as_str(), "ms");
+        assert_eq!(v, None::<String>.to_variant());
+        assert_eq!(Some(&1u32).to_variant(), Some(1u32).to_variant());
+    }
+
+    #[test]
+    fn test_result() {
+        assert_eq!(
+            <Result<u32, String>>::static_variant_type().as_str(),
+            "(mums)"
+        );
+
+        let ok = Ok::<u32, String>(42).to_variant();
+        let err = Err::<u32, String>(String::from("failed")).to_variant();
+        assert_eq!(ok.type_(), err.type_());
+        assert_eq!(ok.get::<Result<u32, String>>(), Some(Ok(42)));
+        assert_eq!(
+            err.get::<Result<u32, String>>(),
+            Some(Err(String::from("failed")))
+        );
+        assert_eq!(Variant::from(Ok::<u32, String>(42)), ok);
+
+        let results = vec![Ok(1u32), Err(String::from("failed"))].to_variant();
+        assert_eq!(results.type_().as_str(), "a(mums)");
+
+        assert!((None::<u32>, None::<String>)
+            .to_variant()
+            .get::<Result<u32, String>>()
+            .is_none());
+        assert!((Some(1u32), Some("failed"))
+            .to_variant()
+            .get::<Result<u32, String>>()
+            .is_none());
+        assert!(42u32.to_variant().get::<Result<u32, String>>().is_none());
+    }
+
+    #[test]
+    fn test_i8() {
+        let v = (-1i8).to_variant();
+        assert_eq!(v.type_(), VariantTy::BYTE);
+        assert_eq!(v.get::<u8>(), Some(0xff));
+        assert_eq!(v.get::<i8>(), Some(-1));
+        assert_eq!(0x80u8.to_variant().get::<i8>(), Some(i8::MIN));
+        assert_eq!(1u16.to_variant().get::<i8>(), None);
+
+        let a = [0xffu8, 0x7f, 0x80].to_variant();
+        assert_eq!(a.fixed_array::<i8>().unwrap(), &[-1, i8::MAX, i8::MIN]);
+        assert_eq!(a.get::<Vec<i8>>(), Some(vec![-1, i8::MAX, i8::MIN]));
+        assert_eq!(
+            Variant::array_from_fixed_array(&[-2i8]),
+            [0xfeu8].to_variant()
+        );
+    }
+
+    #[test]
+    fn test_value_roundtrip() {
+        use crate::value::ValueTypeMismatchOrNoneError;
+
+        let v = (1u32, "foo").to_variant();
+        let value = v.to_value();
+        assert_eq!(value.get::<Variant>(), Ok(v.clone()));
+        assert_eq!(value.get::<Option<Variant>>(), Ok(Some(v.clone())));
+        let ptr: *mut ffi::GVariant = v.to_glib_none().0;
+        let stored: *mut ffi::GVariant = value.get::<Variant>().unwrap().to_glib_none().0;
+        assert_eq!(stored, ptr);
+        assert_eq!(crate::Value::from(v.clone()).get::<Variant>(), Ok(v));
+
+        let value = None::<Variant>.to_value();
+        assert_eq!(value.type_(), Variant::static_type());
+        assert_eq!(value.get::<Option<Variant>>(), Ok(None));
+        assert!(matches!(
+            value.get::<Variant>(),
+            Err(ValueTypeMismatchOrNoneError::UnexpectedNone)
+        ));
+
+        let value = crate::Value::for_value_type::<Variant>();
+        assert_eq!(value.get::<Option<Variant>>(), Ok(None));
+        assert!(matches!(
+            Variant::try_from(&value),
+            Err(ValueTypeMismatchOrNoneError::UnexpectedNone)
+        ));
+    }
+
+    #[test]
+    fn test_value_conversions() {
+        use crate::value::ValueTypeMismatchOrNoneError;
+
+        let v = "foo".to_variant();
+        let value = crate::Value::from(v.clone());
+        assert_eq!(Variant::try_from(&value), Ok(v));
+        assert!(matches!(
+            Variant::try_from(&1u32.to_value()),
+            Err(ValueTypeMismatchOrNoneError::WrongValueType(_))
+        ));
+    }
+
+    #[test]
+    fn test_maybe_value() {
+        let m = Some("foo").to_variant();
+        assert_eq!(m.maybe_value(), Some(Some("foo".to_variant())));
+        assert_eq!(None::<String>.to_variant().maybe_value(), Some(None));
+        assert_eq!("foo".to_variant().maybe_value(), None);
+    }
+
+    #[test]
+    fn test_try_from_maybe() {
+        let child = vec![Some(1u32)].to_variant();
+        let m = Variant::try_from_maybe::<Vec<Option<u32>>>(Some(&child)).unwrap();
+        assert_eq!(m.type_().as_str(), "mamu");
+
+        let err = Variant::try_from_maybe::<Vec<u32>>(Some(&child)).unwrap_err();
+        assert_eq!(err.expected.as_str(), "au");
+        assert_eq!(err.actual.as_str(), "amu");
+
+        let m = Variant::try_from_maybe::<Option<u32>>(None).unwrap();
+        assert_eq!(m.type_().as_str(), "mmu");
+        assert!(m.as_maybe().is_none());
+
+        struct Indefinite;
+        impl StaticVariantType for Indefinite {
+            fn static_variant_type() -> Cow<'static, VariantTy> {
+                Cow::Borrowed(VariantTy::ANY)
+            }
+        }
+        let err = Variant::try_from_maybe::<Indefinite>(None).unwrap_err();
+        assert_eq!(err.actual, VariantTy::ANY);
+        assert_eq!(
+            err.to_string(),
+            "Type mismatch: Expected a definite type got '*'"
+        );
+    }
+
     #[test]
     fn test_btreemap() {
         assert_eq!(
@@ -2413,6 +4604,26 @@ mod tests {
         assert_eq!(BTreeMap::from_variant(&v).unwrap(), m);
     }
 
+    #[test]
+    fn test_dict_entry_tuple() {
+        let entry = DictEntry::from(("foo", 1u32));
+        assert_eq!(*entry.key(), "foo");
+        assert_eq!(<(&str, u32)>::from(entry), ("foo", 1u32));
+
+        let dict = [("foo", 1u32), ("bar", 2u32)]
+            .into_iter()
+            .map(DictEntry::from)
+            .collect::<Variant>();
+        assert_eq!(dict.type_().as_str(), "a{su}");
+        assert_eq!(dict.n_children(), 2);
+
+        let empty = std::iter::empty::<(String, u32)>()
+            .map(DictEntry::from)
+            .collect::<Variant>();
+        assert_eq!(empty.type_().as_str(), "a{su}");
+        assert_eq!(empty.n_children(), 0);
+    }
+
     #[test]
     fn test_get() -> Result<(), Box<dyn std::error::Error>> {
         let u = 42u32.to_variant();
@@ -2424,6 +4635,111 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_get_or() {
+        let u = 42u32.to_variant();
+        assert_eq!(u.get_or(7u32), 42);
+        assert_eq!(u.get_or(7i32), 7);
+        assert_eq!(u.get_or_else(|| String::from("default")), "default");
+    }
+
+    #[test]
+    fn test_try_get_child_error() {
+        let v = ("foo", vec![(1u8, 2u32)]).to_variant();
+        assert!(v.try_get::<(String, Vec<(u8, u32)>)>().is_ok());
+
+        let err = v.try_get::<(String, Vec<(u8, i32)>)>().unwrap_err();
+        assert_eq!(err.actual.as_str(), "u");
+        assert_eq!(err.expected.as_str(), "i");
+
+        let err = v.try_get::<(String, u32)>().unwrap_err();
+        assert_eq!(err.actual.as_str(), "a(yu)");
+        assert_eq!(err.expected.as_str(), "u");
+        assert_eq!(err.field(), Some(1));
+
+        let err = v.try_get::<(String, Vec<(u8, u32)>, u8)>().unwrap_err();
+        assert_eq!(err.actual.as_str(), "(sa(yu))");
+        assert_eq!(err.expected.as_str(), "(sa(yu)y)");
+        assert_eq!(err.field(), None);
+
+        let m = HashMap::from([("foo", 1u32)]).to_variant();
+        let err = m.try_get::<HashMap<String, String>>().unwrap_err();
+        assert_eq!(err.actual.as_str(), "u");
+        assert_eq!(err.expected.as_str(), "s");
+    }
+
+    #[test]
+    fn test_child_get_checked() {
+        let v = (1u32, "foo").to_variant();
+        assert_eq!(v.child_get_checked::<u32>(0), Ok(1));
+        assert_eq!(v.child_get_checked::<String>(1).as_deref(), Ok("foo"));
+        assert_eq!(
+            v.child_get_checked::<u32>(2),
+            Err(VariantChildError::OutOfRange {
+                index: 2,
+                n_children: 2
+            })
+        );
+        assert!(matches!(
+            v.child_get_checked::<u32>(1),
+            Err(VariantChildError::TypeMismatch(_))
+        ));
+        assert_eq!(
+            1u32.to_variant().child_get_checked::<u32>(0),
+            Err(VariantChildError::NotContainer(
+                VariantTy::UINT32.to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_try_get_error_path() {
+        let v = (1u8, 2u8, vec![1u32, 2, 3, 4, 5, 6]).to_variant();
+        let err = v.try_get::<(u8, u8, Vec<i32>)>().unwrap_err();
+        assert_eq!(err.path, [".2", "[0]"]);
+
+        let v = (1u8, 2u8, vec![("a", 1u32), ("b", 2u32)]).to_variant();
+        let err = v.try_get::<(u8, u8, Vec<(String, String)>)>().unwrap_err();
+        assert_eq!(err.path.concat(), ".2[0].1");
+        assert_eq!(
+            err.to_string(),
+            "Type mismatch at .2[0].1: Expected 's' got 'u'"
+        );
+
+        let m = BTreeMap::from([("foo", 1u32)]).to_variant();
+        let err = m.try_get::<BTreeMap<String, String>>().unwrap_err();
+        assert_eq!(err.path, ["{foo}"]);
+        let err = m.try_get::<BTreeMap<u32, u32>>().unwrap_err();
+        assert_eq!(err.path, ["[0]", ".0"]);
+
+        let err = 1u32.to_variant().try_get::<String>().unwrap_err();
+        assert!(err.path.is_empty());
+    }
+
+    #[test]
+    fn test_try_get_tuple_field() {
+        let v = (0u8, 1u8, 2u8, 3u8, 4u8, 5u8, 6u8, "7", 8u8, 9u8).to_variant();
+        let err = v
+            .try_get::<(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8)>()
+            .unwrap_err();
+        assert_eq!(err.field(), Some(7));
+        assert_eq!(err.actual.as_str(), "s");
+        assert_eq!(err.expected.as_str(), "y");
+        assert_eq!(err.path, [".7"]);
+        assert_eq!(err.to_string(), "Type mismatch at .7: Expected 'y' got 's'");
+
+        let v = (1u8, vec![1u8]).to_variant();
+        let err = v.try_get::<(u8, Vec<u32>)>().unwrap_err();
+        assert_eq!(err.path, [".1", "[0]"]);
+        assert_eq!(err.field(), Some(1));
+        let err = vec![1u8].to_variant().try_get::<Vec<u32>>().unwrap_err();
+        assert_eq!(err.field(), None);
+
+        let err = 1u8.to_variant().try_get::<u32>().unwrap_err();
+        assert_eq!(err.field(), None);
+        assert_eq!(err.to_string(), "Type mismatch: Expected 'u' got 'y'");
+    }
+
     #[test]
     fn test_byteswap() {
         let u = 42u32.to_variant();
@@ -2445,6 +4761,800 @@ mod tests {
         assert!(u.try_child_get::<String>(0).unwrap().is_none());
     }
 
+    #[test]
+    fn test_at() {
+        let v = ("foo", vec![(1u8, 2u32)]).to_variant();
+        assert_eq!(v.at(1).at(0).at(1), 2u32.to_variant());
+        assert_eq!(v.get_at(0), Some("foo".to_variant()));
+        assert!(v.get_at(2).is_none());
+        assert!(v.at(0).get_at(0).is_none());
+    }
+
+    #[test]
+    fn test_children() {
+        let a = ["foo", "bar"].to_variant();
+        assert_eq!(a.children(), ["foo".to_variant(), "bar".to_variant()]);
+        assert_eq!(a.try_children().map(|c| c.len()), Some(2));
+        assert!(Vec::<u32>::new().to_variant().children().is_empty());
+        assert!(42u32.to_variant().try_children().is_none());
+    }
+
+    #[test]
+    fn test_from_bytes_no_copy() {
+        let bytes = Bytes::from_owned(b"hello\0".to_vec());
+        let data_ptr = <[u8]>::as_ptr(&bytes);
+
+        let v = Variant::from_bytes::<String>(&bytes);
+        assert_eq!(v.str(), Some("hello"));
+        assert_eq!(v.data().as_ptr(), data_ptr);
+
+        // The variant holds its own reference to the data
+        drop(bytes);
+        assert_eq!(v.str(), Some("hello"));
+        assert_eq!(v.data_as_bytes(), b"hello\0"[..]);
+    }
+
+    #[test]
+    fn test_bytestring_from_str() {
+        let v = Variant::bytestring_from_str("/tmp/foo");
+        assert_eq!(v.type_(), VariantTy::BYTE_STRING);
+        assert_eq!(v.fixed_array::<u8>().unwrap(), b"/tmp/foo\0");
+        assert_eq!(
+            v.get::<std::path::PathBuf>().unwrap(),
+            std::path::Path::new("/tmp/foo")
+        );
+
+        let v = Variant::bytestring_from_str("a\0b");
+        assert_eq!(v.type_(), VariantTy::BYTE_STRING);
+        assert_eq!(v.fixed_array::<u8>().unwrap(), b"a\0b\0");
+    }
+
+    #[test]
+    fn test_bytes() {
+        let bytes = Bytes::from_static(b"\x00\x01binary");
+        let v = bytes.to_variant();
+        assert_eq!(v.type_(), VariantTy::BYTE_STRING);
+        assert_eq!(v.fixed_array::<u8>().unwrap(), &*bytes);
+        assert_eq!(v.data().as_ptr(), bytes.as_ref().as_ptr());
+
+        let b = v.get::<Bytes>().unwrap();
+        assert_eq!(b, bytes);
+        assert_eq!(b.as_ref().as_ptr(), bytes.as_ref().as_ptr());
+
+        assert_eq!(Variant::from(bytes.clone()), v);
+        assert_eq!(
+            vec![1u8, 2].to_variant().get::<Bytes>().unwrap(),
+            [1u8, 2][..]
+        );
+        assert_eq!(vec![1u32, 2].to_variant().get::<Bytes>(), None);
+    }
+
+    #[test]
+    fn test_flags() {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        struct Perms(u32);
+
+        impl From<u32> for Perms {
+            fn from(v: u32) -> Self {
+                Perms(v)
+            }
+        }
+
+        impl From<Perms> for u32 {
+            fn from(v: Perms) -> Self {
+                v.0
+            }
+        }
+
+        let v = Flags(Perms(0b101)).to_variant();
+        assert_eq!(v.type_(), VariantTy::UINT32);
+        assert_eq!(v.get::<u32>(), Some(0b101));
+        assert_eq!(v.get::<Flags<Perms>>(), Some(Flags(Perms(0b101))));
+        assert_eq!(Variant::from(Flags(Perms(8))), 8u32.to_variant());
+
+        // Unknown bits are passed through
+        assert_eq!(
+            u32::MAX.to_variant().get::<Flags<Perms>>(),
+            Some(Flags(Perms(u32::MAX)))
+        );
+        assert_eq!(1i32.to_variant().get::<Flags<Perms>>(), None);
+    }
+
+    #[test]
+    fn test_range() {
+        use std::ops::{Range, RangeInclusive};
+
+        let v = (1u32..5).to_variant();
+        assert_eq!(v.type_().as_str(), "(uu)");
+        assert_eq!(v, (1u32, 5u32).to_variant());
+        assert_eq!(v.get::<Range<u32>>(), Some(1..5));
+        assert_eq!(v.get::<RangeInclusive<u32>>(), Some(1..=5));
+
+        let v = (-2.5f64..=2.5).to_variant();
+        assert_eq!(v.get::<RangeInclusive<f64>>(), Some(-2.5..=2.5));
+
+        assert_eq!((1u32, 5u32, 7u32).to_variant().get::<Range<u32>>(), None);
+        assert_eq!((1u32,).to_variant().get::<Range<u32>>(), None);
+        assert_eq!((1u32, 5i32).to_variant().get::<Range<u32>>(), None);
+        assert_eq!(v.get::<Range<u32>>(), None);
+    }
+
+    #[test]
+    fn test_utf32() {
+        let v = Variant::from_utf32("añ€😀");
+        assert_eq!(v.type_().as_str(), "au");
+        assert_eq!(
+            v.fixed_array::<u32>().unwrap(),
+            &[0x61, 0xf1, 0x20ac, 0x1f600]
+        );
+        assert_eq!(v.to_utf32_string().as_deref(), Some("añ€😀"));
+        assert_eq!(
+            Variant::from_utf32("").to_utf32_string().as_deref(),
+            Some("")
+        );
+
+        assert_eq!([0xd800u32].to_variant().to_utf32_string(), None);
+        assert_eq!([0x110000u32].to_variant().to_utf32_string(), None);
+        assert_eq!("abc".to_variant().to_utf32_string(), None);
+    }
+
+    #[test]
+    fn test_ip_addr() {
+        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+        let v4 = Ipv4Addr::new(192, 168, 0, 1);
+        let v = v4.to_variant();
+        assert_eq!(v.type_(), VariantTy::BYTE_STRING);
+        assert_eq!(v.fixed_array::<u8>().unwrap(), &[192, 168, 0, 1]);
+        assert_eq!(v.get::<Ipv4Addr>(), Some(v4));
+        assert_eq!(v.get::<Ipv6Addr>(), None);
+        assert_eq!([1u8, 2, 3, 4, 5].to_variant().get::<Ipv4Addr>(), None);
+
+        let v6 = Ipv6Addr::LOCALHOST;
+        assert_eq!(v6.to_variant().n_children(), 16);
+        assert_eq!(v6.to_variant().get::<Ipv6Addr>(), Some(v6));
+
+        assert_eq!(IpAddr::static_variant_type().as_str(), "(bay)");
+        let ip = IpAddr::V4(v4);
+        assert_eq!(ip.to_variant().get::<IpAddr>(), Some(ip));
+        let ip = IpAddr::V6(v6);
+        assert_eq!(ip.to_variant().get::<IpAddr>(), Some(ip));
+        assert_eq!((true, v4).to_variant().get::<IpAddr>(), None);
+        assert_eq!((false, v6).to_variant().get::<IpAddr>(), None);
+
+        assert_eq!(SocketAddr::static_variant_type().as_str(), "((bay)q)");
+        let addr: SocketAddr = "[::1]:8080".parse().unwrap();
+        assert_eq!(addr.to_variant().get::<SocketAddr>(), Some(addr));
+        let addr: SocketAddr = "10.0.0.1:53".parse().unwrap();
+        assert_eq!(addr.to_variant().get::<SocketAddr>(), Some(addr));
+    }
+
+    #[test]
+    fn test_bytes_if_type() {
+        let src = ("foo", 1u32).to_variant();
+        let v = Variant::from_bytes::<(String, u32)>(&src.data_as_bytes());
+        assert_eq!(v.bytes_if_type::<(String, u32)>().unwrap(), v.data());
+
+        let err = v.bytes_if_type::<(String, i32)>().unwrap_err();
+        assert_eq!(err.actual.as_str(), "(su)");
+        assert_eq!(err.expected.as_str(), "(si)");
+        assert!(v.bytes_if_type::<Variant>().is_err());
+    }
+
+    #[test]
+    fn test_store_to_vec() {
+        let a = ("test", 1u8, 2u32).to_variant();
+
+        // Misaligned prefix for the `u` member.
+        let mut out = vec![0xffu8; 3];
+        assert_eq!(a.store_to_vec(&mut out), a.size());
+        assert_eq!(out.len(), 3 + a.size());
+        assert_eq!(&out[..3], &[0xff; 3]);
+        assert_eq!(&out[3..], a.data());
+
+        let mut out = Vec::new();
+        assert_eq!(().to_variant().store_to_vec(&mut out), 1);
+        assert_eq!(out, [0]);
+    }
+
+    #[test]
+    fn test_print_pretty() {
+        assert_eq!(5u32.to_variant().print_pretty(2), "uint32 5");
+        assert_eq!(Vec::<u32>::new().to_variant().print_pretty(2), "@au []");
+
+        let v = (1u8, ["a", "b"]).to_variant();
+        assert_eq!(
+            v.print_pretty(2),
+            "(\n  byte 0x01,\n  [\n    'a',\n    'b',\n  ],\n)"
+        );
+
+        let mut map = BTreeMap::new();
+        map.insert("x", 1i32.to_variant());
+        map.insert("y", None::<bool>.to_variant());
+        assert_eq!(
+            map.to_variant().print_pretty(4),
+            "{\n    'x': <1>,\n    'y': <nothing>,\n}"
+        );
+    }
+
+    #[test]
+    fn test_eq_str() {
+        assert!("foo".to_variant() == "foo");
+        assert!("foo".to_variant() != "bar");
+        assert!("foo".to_variant() == *"foo");
+        assert!(ObjectPath::try_from("/foo").unwrap().to_variant() == "/foo");
+        assert!(Signature::try_from("as").unwrap().to_variant() == "as");
+        assert!(b"foo".to_variant() != "foo");
+        assert!(Some("foo").to_variant() != "foo");
+        assert!("foo".to_variant().to_variant() != "foo");
+    }
+
+    #[test]
+    fn test_to_dict() {
+        let v = HashMap::from([("foo", 1u32.to_variant())]).to_variant();
+        let dict = v.to_dict().unwrap();
+        assert_eq!(dict.lookup::<u32>("foo").unwrap(), Some(1));
+        assert!(!dict.contains("bar"));
+
+        assert!(HashMap::from([("foo", 1u32)])
+            .to_variant()
+            .to_dict()
+            .is_none());
+        assert!(1u32.to_variant().to_dict().is_none());
+    }
+
+    #[test]
+    fn test_structurally_eq() {
+        let empty_as = Vec::<String>::new().to_variant();
+        let empty_ai = Vec::<i32>::new().to_variant();
+        assert_ne!(empty_as, empty_ai);
+        assert!(empty_as.structurally_eq(&empty_ai));
+        assert!(None::<String>
+            .to_variant()
+            .structurally_eq(&None::<u8>.to_variant()));
+        assert!((1u8, Vec::<u8>::new())
+            .to_variant()
+            .structurally_eq(&(1u8, Vec::<bool>::new()).to_variant()));
+
+        assert!([1i32].to_variant().structurally_eq(&[1i32].to_variant()));
+        assert!(![1i32].to_variant().structurally_eq(&[1i64].to_variant()));
+        assert!(![1i32].to_variant().structurally_eq(&[2i32].to_variant()));
+        assert!(!empty_as.structurally_eq(&["a"].to_variant()));
+        assert!(!empty_as.structurally_eq(&None::<String>.to_variant()));
+    }
+
+    #[test]
+    fn test_with_type() {
+        let v = [1u8, 2].to_variant();
+        let t = v.with_type(VariantTy::new("(yy)").unwrap()).unwrap();
+        assert_eq!(t, (1u8, 2u8).to_variant());
+        assert_eq!(t.with_type(VariantTy::BYTE_STRING).unwrap(), v);
+
+        let d = HashMap::from([("a", 1u32.to_variant())]).to_variant();
+        assert_eq!(d.with_type(VariantTy::VARDICT).unwrap(), d);
+
+        assert!(v.with_type(VariantTy::new("(yyy)").unwrap()).is_err());
+        assert!(v.with_type(VariantTy::ARRAY).is_err());
+        let bools = [true, false]
+            .to_variant()
+            .with_type(VariantTy::new("(bb)").unwrap());
+        assert_eq!(bools.unwrap(), (true, false).to_variant());
+        assert!([2u8, 2]
+            .to_variant()
+            .with_type(VariantTy::new("(bb)").unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn test_is_exact_type() {
+        let v = HashMap::<String, Variant>::new().to_variant();
+        assert!(v.is_exact_type(VariantTy::VARDICT));
+        assert!(v.is_type(VariantTy::DICTIONARY));
+        assert!(!v.is_exact_type(VariantTy::DICTIONARY));
+        assert!(v.is_type(VariantTy::ANY));
+        assert!(!v.is_exact_type(VariantTy::ANY));
+        assert!(!v.is_exact_type(VariantTy::new("a{ss}").unwrap()));
+    }
+
+    #[test]
+    fn test_into_get() {
+        let v = 5u32.to_variant();
+        let (v, err) = v.into_get::<String>().unwrap_err();
+        assert_eq!(err.expected, VariantTy::STRING);
+        assert_eq!(v.into_get::<u32>().unwrap(), 5);
+    }
+
+    #[test]
+    fn test_compare_basic() {
+        let mut column = [3u64, 1, 2].map(|v| v.to_variant());
+        column.sort_by(|a, b| a.compare_basic(b).unwrap());
+        assert_eq!(column, [1u64, 2, 3].map(|v| v.to_variant()));
+
+        assert_eq!(
+            "a".to_variant().compare_basic(&"b".to_variant()),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            2i32.to_variant().compare_basic(&2i32.to_variant()),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(1u64.to_variant().compare_basic(&1u32.to_variant()), None);
+        let t = (1u8,).to_variant();
+        assert_eq!(t.compare_basic(&t), None);
+    }
+
+    #[test]
+    fn test_expect_type() {
+        let v = (1u8, "foo").to_variant();
+        assert_eq!(v.expect_type::<(u8, String)>(), Ok(&v));
+        let err = v.expect_type::<u32>().unwrap_err();
+        assert_eq!(err.actual.as_str(), "(ys)");
+        assert_eq!(err.expected, VariantTy::UINT32);
+    }
+
+    #[test]
+    fn test_check_type() {
+        let v = ["a", "b"].to_variant();
+        assert!(v.check_type(VariantTy::STRING_ARRAY).is_ok());
+        assert!(v.check_type(VariantTy::ARRAY).is_ok());
+        let err = v.check_type(VariantTy::new("ai").unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "Type mismatch: Expected 'ai' got 'as'");
+    }
+
+    #[test]
+    fn test_eq_value() {
+        assert!(5u32.to_variant().eq_value(&5u32));
+        assert!(!5u32.to_variant().eq_value(&6u32));
+        assert!(!5u32.to_variant().eq_value(&5i32));
+        assert!("foo".to_variant().eq_value(&String::from("foo")));
+        assert!((1u8, true).to_variant().eq_value(&(1u8, true)));
+        assert!(!(1u8, true).to_variant().eq_value(&(1u8, false)));
+    }
+
+    #[test]
+    fn test_scalar_getters() {
+        assert_eq!(true.to_variant().boolean(), Some(true));
+        assert_eq!(7u8.to_variant().byte(), Some(7));
+        assert_eq!((-3i16).to_variant().int16(), Some(-3));
+        assert_eq!(3u16.to_variant().uint16(), Some(3));
+        assert_eq!((-5i32).to_variant().int32(), Some(-5));
+        assert_eq!(5u32.to_variant().uint32(), Some(5));
+        assert_eq!(i64::MIN.to_variant().int64(), Some(i64::MIN));
+        assert_eq!(u64::MAX.to_variant().uint64(), Some(u64::MAX));
+        assert_eq!(1.5f64.to_variant().double(), Some(1.5));
+
+        assert_eq!(7u8.to_variant().uint32(), None);
+        assert_eq!(5u32.to_variant().int32(), None);
+        assert_eq!(1u8.to_variant().boolean(), None);
+        assert_eq!("foo".to_variant().byte(), None);
+    }
+
+    #[test]
+    fn test_array_element_type() {
+        assert_eq!(
+            ["a", "b"].to_variant().array_element_type(),
+            Some(VariantTy::STRING)
+        );
+        assert_eq!(
+            Variant::empty_array(VariantTy::VARIANT).array_element_type(),
+            Some(VariantTy::VARIANT)
+        );
+        assert_eq!(Some(1u32).to_variant().array_element_type(), None);
+        assert_eq!(1u32.to_variant().array_element_type(), None);
+    }
+
+    #[test]
+    fn test_vec_rejects_non_arrays() {
+        assert_eq!((1u32, 2u32, 3u32).to_variant().get::<Vec<u32>>(), None);
+        assert_eq!(Some(1u32).to_variant().get::<Vec<u32>>(), None);
+        assert!((1u32, 2u32).to_variant().try_get::<Vec<u32>>().is_err());
+        assert_eq!(
+            [1u32, 2, 3].to_variant().get::<Vec<u32>>(),
+            Some(vec![1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn test_array_ref() {
+        let arr: &[u32; 3] = &[1, 2, 3];
+        let v = arr.to_variant();
+        assert_eq!(v.type_(), <[u32; 3]>::static_variant_type());
+        assert_eq!(v, arr.as_slice().to_variant());
+        assert_eq!(v.get::<Vec<u32>>(), Some(vec![1, 2, 3]));
+
+        let empty: [&str; 0] = [];
+        assert_eq!(empty.to_variant().type_(), VariantTy::STRING_ARRAY);
+    }
+
+    #[test]
+    fn test_assert_type_string() {
+        assert_type_string::<(String, u32)>("(su)");
+        assert_type_string::<HashMap<String, Variant>>("a{sv}");
+        assert_type_string::<str>("s");
+    }
+
+    #[test]
+    #[should_panic(expected = "is '(us)', expected '(su)'")]
+    fn test_assert_type_string_mismatch() {
+        assert_type_string::<(u32, String)>("(su)");
+    }
+
+    #[test]
+    fn test_estimate_serialized_size() {
+        assert_eq!(estimate_serialized_size(VariantTy::BYTE, 5), Some(5));
+        assert_eq!(estimate_serialized_size(VariantTy::DOUBLE, 0), Some(0));
+        assert_eq!(estimate_serialized_size(VariantTy::STRING, 1), None);
+        assert_eq!(estimate_serialized_size(VariantTy::ANY, 1), None);
+        assert_eq!(
+            estimate_serialized_size(VariantTy::UINT64, usize::MAX),
+            None
+        );
+
+        let v = [(1u8, 2u32), (3u8, 4u32)].to_variant();
+        let ty = v.type_().element();
+        assert_eq!(estimate_serialized_size(ty, 2), Some(v.size()));
+        assert_eq!(estimate_serialized_size(ty, 2), Some(16));
+
+        let v = [(1u64, true, 3i16)].to_variant();
+        assert_eq!(
+            estimate_serialized_size(v.type_().element(), 1),
+            Some(v.size())
+        );
+
+        let v = [DictEntry::new(1u32, 2u8)].to_variant();
+        assert_eq!(
+            estimate_serialized_size(v.type_().element(), 1),
+            Some(v.size())
+        );
+
+        let v = [(), ()].to_variant();
+        assert_eq!(
+            estimate_serialized_size(v.type_().element(), 2),
+            Some(v.size())
+        );
+
+        assert_eq!(
+            estimate_serialized_size(VariantTy::new("(us)").unwrap(), 1),
+            None
+        );
+    }
+
+    #[test]
+    fn test_copy_fixed_array() {
+        let v = [1u32, 2, 3].to_variant();
+        let mut short = [0u32; 2];
+        assert_eq!(v.copy_fixed_array(&mut short), Ok(2));
+        assert_eq!(short, [1, 2]);
+
+        let mut long = [0u32; 5];
+        assert_eq!(v.copy_fixed_array(&mut long), Ok(3));
+        assert_eq!(long, [1, 2, 3, 0, 0]);
+
+        assert!(v.copy_fixed_array(&mut [0i32; 3]).is_err());
+        assert_eq!(
+            Vec::<u32>::new().to_variant().copy_fixed_array(&mut long),
+            Ok(0)
+        );
+    }
+
+    #[test]
+    fn test_fixed_size_vec() {
+        let bytes = (0..=255u8).cycle().take(4096).collect::<Vec<_>>();
+        let v = Variant::from_bytes::<Vec<u8>>(&Bytes::from(&bytes));
+        assert_eq!(v.get::<Vec<u8>>(), Some(bytes.clone()));
+        assert_eq!(v.get::<Vec<i8>>().unwrap().len(), bytes.len());
+        assert_eq!(v.get::<Vec<u16>>(), None);
+
+        let v = vec![1.5f64, -2.0].to_variant();
+        assert_eq!(v.get::<Vec<f64>>(), Some(vec![1.5, -2.0]));
+        assert_eq!(v.get::<Vec<i64>>(), None);
+        let err = v.try_get::<Vec<i64>>().unwrap_err();
+        assert_eq!(err.path, ["[0]"]);
+    }
+
+    #[test]
+    fn test_map_array() {
+        let ids = [1i32, 2, 3].to_variant();
+        let names = ids
+            .map_array(|id| format!("item{}", id.get::<i32>().unwrap()).to_variant())
+            .unwrap();
+        assert_eq!(names, ["item1", "item2", "item3"].to_variant());
+
+        let err = ids
+            .map_array(|id| match id.get::<i32>().unwrap() {
+                2 => 2u8.to_variant(),
+                id => id.to_variant(),
+            })
+            .unwrap_err();
+        assert_eq!(err.path, ["[1]"]);
+        assert_eq!(err.expected, VariantTy::INT32);
+        assert_eq!(err.actual, VariantTy::BYTE);
+
+        let err = Vec::<i32>::new().to_variant().map_array(|v| v).unwrap_err();
+        assert!(!err.actual.is_definite());
+        assert_eq!(
+            err.to_string(),
+            "Type mismatch: Expected a definite type got 'a*'"
+        );
+        let err = (1i32,).to_variant().map_array(|v| v).unwrap_err();
+        assert_eq!(err.to_string(), "Type mismatch: Expected 'a*' got '(i)'");
+    }
+
+    #[test]
+    fn test_from_dynamic() {
+        let value = Value::Tuple(vec![
+            Value::Bool(true),
+            Value::Array(vec![Value::I64(-1), Value::I64(2)]),
+            Value::Dict(vec![
+                (Value::Str("a".into()), Value::F64(1.5)),
+                (Value::Str("b".into()), Value::F64(2.5)),
+            ]),
+            Value::Tuple(vec![]),
+            Value::U64(7),
+        ]);
+        let v = Variant::from_dynamic(&value).unwrap();
+        assert_eq!(v.type_().as_str(), "(baxa{sd}()t)");
+        assert_eq!(v.child_value(1), [-1i64, 2].to_variant());
+        assert_eq!(
+            v.child_value(2).get::<BTreeMap<String, f64>>().unwrap(),
+            BTreeMap::from([("a".into(), 1.5), ("b".into(), 2.5)])
+        );
+
+        let mixed = Value::Array(vec![Value::I64(1), Value::Str("x".into())]);
+        assert!(Variant::from_dynamic(&mixed).is_err());
+        assert!(Variant::from_dynamic(&Value::Array(vec![])).is_err());
+        let bad_key = Value::Dict(vec![(Value::Tuple(vec![]), Value::Bool(true))]);
+        assert!(Variant::from_dynamic(&bad_key).is_err());
+    }
+
+    #[test]
+    fn test_from_array_iter() {
+        let v = Variant::from_array_iter(["a", "b"].iter().copied());
+        assert_eq!(v, ["a", "b"].to_variant());
+
+        let empty = Variant::from_array_iter(std::iter::empty::<u16>());
+        assert_eq!(empty.type_(), VariantTy::new("aq").unwrap());
+        assert_eq!(empty.n_children(), 0);
+    }
+
+    #[test]
+    fn test_from_dict_entries() {
+        let v = Variant::from_dict_entries([("b", 2u32), ("a", 1u32), ("c", 3u32)]);
+        assert_eq!(v.type_().as_str(), "a{su}");
+        let keys = v
+            .iter()
+            .map(|entry| entry.child_value(0).get::<String>().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, ["b", "a", "c"]);
+        assert_eq!(
+            v.get::<HashMap<String, u32>>().unwrap(),
+            HashMap::from([("a".into(), 1), ("b".into(), 2), ("c".into(), 3)])
+        );
+
+        let empty = Variant::from_dict_entries(std::iter::empty::<(String, Variant)>());
+        assert_eq!(empty.type_().as_str(), "a{sv}");
+        assert_eq!(empty.n_children(), 0);
+    }
+
+    #[test]
+    fn test_from_some_none() {
+        let child = (1u32, "foo").to_variant();
+        let m = Variant::from_some(&child);
+        assert_eq!(m.type_().as_str(), "m(us)");
+        assert_eq!(m.as_maybe(), Some(child));
+
+        let m = Variant::from_some(&1u32.to_variant().to_variant());
+        assert_eq!(m.type_().as_str(), "mv");
+
+        let m = Variant::from_none(VariantTy::new("(us)").unwrap());
+        assert_eq!(m.type_().as_str(), "m(us)");
+        assert_eq!(m.as_maybe(), None);
+    }
+
+    #[test]
+    fn test_indefinite_types() {
+        assert!(!VariantTy::new("a*").unwrap().is_definite());
+        assert!(VariantTy::STRING_ARRAY.is_definite());
+
+        assert!(Variant::try_empty_array(VariantTy::ANY).is_err());
+        assert!(Variant::try_empty_array(VariantTy::TUPLE).is_err());
+        let a = Variant::try_empty_array(VariantTy::STRING).unwrap();
+        assert_eq!(a.type_(), VariantTy::STRING_ARRAY);
+
+        assert!(Variant::try_from_none(VariantTy::BASIC).is_err());
+        let m = Variant::try_from_none(VariantTy::UINT32).unwrap();
+        assert_eq!(m.type_().as_str(), "mu");
+        assert_eq!(m.as_maybe(), None);
+    }
+
+    #[test]
+    fn test_sorted_array() {
+        let a = ["b", "c", "a"].to_variant();
+        assert_eq!(a.sorted_array().unwrap(), ["a", "b", "c"].to_variant());
+
+        let a = [3i32, -1, 2].to_variant();
+        assert_eq!(a.sorted_array().unwrap(), [-1i32, 2, 3].to_variant());
+
+        let a = [2.0f64, f64::NAN, -1.0].to_variant();
+        let sorted = a.sorted_array().unwrap().get::<Vec<f64>>().unwrap();
+        assert_eq!(&sorted[..2], &[-1.0, 2.0]);
+        assert!(sorted[2].is_nan());
+
+        let empty = Variant::empty_array(VariantTy::UINT32);
+        assert_eq!(empty.sorted_array().unwrap(), empty);
+
+        let err = [(1u8, 2u8)].to_variant().sorted_array().unwrap_err();
+        assert_eq!(err.expected.as_str(), "a?");
+        assert!(1u32.to_variant().sorted_array().is_err());
+        assert!([1u32.to_variant()].to_variant().sorted_array().is_err());
+    }
+
+    #[test]
+    fn test_try_array_from_iter_with_type() {
+        let ty = VariantTy::new("(su)").unwrap();
+        let children = [("a", 1u32).to_variant(), ("b", 2u32).to_variant()];
+        let a = Variant::try_array_from_iter_with_type(ty, &children).unwrap();
+        assert_eq!(a.type_().as_str(), "a(su)");
+        assert_eq!(a.n_children(), 2);
+
+        let children = [("a", 1u32).to_variant(), ("b", 2i32).to_variant()];
+        let err = Variant::try_array_from_iter_with_type(ty, &children).unwrap_err();
+        assert_eq!(err.actual.as_str(), "(si)");
+        assert_eq!(err.expected.as_str(), "(su)");
+
+        let empty = Variant::try_array_from_iter_with_type(ty, Vec::<Variant>::new()).unwrap();
+        assert_eq!(empty.type_().as_str(), "a(su)");
+    }
+
+    #[test]
+    fn test_try_array_from_iter() {
+        let a = Variant::try_array_from_iter::<u32>([1u32.to_variant(), 2u32.to_variant()]);
+        assert_eq!(a.unwrap(), [1u32, 2].to_variant());
+
+        let err =
+            Variant::try_array_from_iter::<u32>([1u32.to_variant(), "x".to_variant()]).unwrap_err();
+        assert_eq!(err.actual, VariantTy::STRING);
+        assert_eq!(err.expected, VariantTy::UINT32);
+    }
+
+    #[test]
+    fn test_concat_arrays() {
+        let a = ["a", "b"].to_variant();
+        let b = ["c"].to_variant();
+        assert_eq!(a.concat_arrays(&b).unwrap(), ["a", "b", "c"].to_variant());
+
+        let empty = Variant::empty_array(VariantTy::STRING);
+        assert_eq!(a.concat_arrays(&empty).unwrap(), a);
+        let both_empty = empty.concat_arrays(&empty).unwrap();
+        assert_eq!(both_empty.type_(), VariantTy::STRING_ARRAY);
+        assert_eq!(both_empty.n_children(), 0);
+
+        let err = a.concat_arrays(&[1i32].to_variant()).unwrap_err();
+        assert_eq!(err.actual.as_str(), "ai");
+        assert_eq!(err.expected.as_str(), "as");
+
+        let err = 1u32.to_variant().concat_arrays(&a).unwrap_err();
+        assert_eq!(err.actual.as_str(), "u");
+    }
+
+    #[test]
+    fn test_iter_indexed() {
+        let v = ("a", 1u32, true).to_variant();
+        let items: Vec<_> = v.iter_indexed().collect();
+        assert_eq!(
+            items,
+            [
+                (0, "a".to_variant()),
+                (1, 1u32.to_variant()),
+                (2, true.to_variant())
+            ]
+        );
+        assert_eq!(v.iter_indexed().len(), 3);
+        assert_eq!(v.iter_indexed().next_back(), Some((2, true.to_variant())));
+    }
+
+    #[test]
+    fn test_child_values_range() {
+        let a = [1u32, 2, 3, 4, 5].to_variant();
+        assert_eq!(
+            a.child_values_range(1..3),
+            [2u32.to_variant(), 3u32.to_variant()]
+        );
+        assert_eq!(a.child_values_range(3..5).len(), 2);
+        assert!(a.child_values_range(2..2).is_empty());
+        assert!(a.child_values_range(5..5).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_child_values_range_out_of_bounds() {
+        let a = [1u32, 2, 3].to_variant();
+        let _ = a.child_values_range(1..4);
+    }
+
+    #[test]
+    fn test_with_child_replaced() {
+        let t = (1u8, "foo", true).to_variant();
+        let r = t.with_child_replaced(1, &"bar".to_variant()).unwrap();
+        assert_eq!(r, (1u8, "bar", true).to_variant());
+        assert_eq!(t, (1u8, "foo", true).to_variant());
+        let err = t.with_child_replaced(2, &1u8.to_variant()).unwrap_err();
+        assert_eq!(err.path, [".2"]);
+
+        let a = [1u32, 2, 3].to_variant();
+        let r = a.with_child_replaced(2, &5u32.to_variant()).unwrap();
+        assert_eq!(r, [1u32, 2, 5].to_variant());
+        let err = a.with_child_replaced(0, &5i32.to_variant()).unwrap_err();
+        assert_eq!(err.path, ["[0]"]);
+
+        let e = DictEntry::new("a", 1u32).to_variant();
+        let r = e.with_child_replaced(0, &"b".to_variant()).unwrap();
+        assert_eq!(r, DictEntry::new("b", 1u32).to_variant());
+
+        let m = Some(1u32).to_variant();
+        let r = m.with_child_replaced(0, &2u32.to_variant()).unwrap();
+        assert_eq!(r, Some(2u32).to_variant());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_with_child_replaced_out_of_range() {
+        let _ = (1u8, 2u8)
+            .to_variant()
+            .with_child_replaced(2, &3u8.to_variant());
+    }
+
+    #[test]
+    fn test_iter_as_strings() {
+        let v = (1u8, "foo", [true]).to_variant();
+        assert_eq!(
+            v.iter_as_strings().collect::<Vec<_>>(),
+            ["byte 0x01", "'foo'", "[true]"]
+        );
+        let v = Variant::from_variant(&5u32.to_variant());
+        assert_eq!(v.iter_as_strings().collect::<Vec<_>>(), ["uint32 5"]);
+    }
+
+    #[test]
+    fn test_deep_count() {
+        assert_eq!(1u32.to_variant().deep_count(), 0);
+        assert_eq!([1u32, 2, 3].to_variant().deep_count(), 3);
+        assert_eq!(Vec::<u32>::new().to_variant().deep_count(), 0);
+        assert_eq!((1u8, (2u8, 3u8)).to_variant().deep_count(), 4);
+        assert_eq!(vec![vec!["a"], vec!["b", "c"]].to_variant().deep_count(), 5);
+        assert_eq!(1u32.to_variant().to_variant().deep_count(), 1);
+    }
+
+    #[test]
+    fn test_validate_depth() {
+        assert!(1u32.to_variant().validate_depth(0).is_ok());
+        assert!(Vec::<u32>::new().to_variant().validate_depth(0).is_ok());
+        assert!([1u32].to_variant().validate_depth(0).is_err());
+        assert!([1u32].to_variant().validate_depth(1).is_ok());
+
+        let nested = vec![vec![Some(1u8)]].to_variant();
+        assert!(nested.validate_depth(2).is_err());
+        assert!(nested.validate_depth(3).is_ok());
+
+        let mut deep = 1u8.to_variant();
+        for _ in 0..100 {
+            deep = deep.to_variant();
+        }
+        assert!(deep.validate_depth(64).is_err());
+        assert!(deep.validate_depth(100).is_ok());
+    }
+
+    #[test]
+    fn test_as_ref_bytes() {
+        fn len(data: impl AsRef<[u8]>) -> usize {
+            data.as_ref().len()
+        }
+
+        let v = ("test", 1u8, 2u32).to_variant();
+        assert_eq!(AsRef::<[u8]>::as_ref(&v), v.data());
+        assert_eq!(len(&v), v.size());
+    }
+
     #[test]
     fn test_serialize() {
         let a = ("test", 1u8, 2u32).to_variant();
@@ -2468,6 +5578,28 @@ mod tests {
         assert_eq!(a, c);
     }
 
+    #[test]
+    fn test_data_as_bytes_shared() {
+        let a = ("test", 1u8, 2u32).to_variant();
+        let bytes = a.data_as_bytes();
+        assert_eq!(&*bytes, a.data());
+        assert_eq!(<[u8]>::as_ptr(&bytes), a.data().as_ptr());
+        drop(a);
+        let b = Variant::from_bytes::<(String, u8, u32)>(&bytes);
+        assert_eq!(b.get::<(String, u8, u32)>(), Some(("test".into(), 1, 2)));
+    }
+
+    #[test]
+    fn test_needs_normalization() {
+        let a = ("test", 1u8, 2u32).to_variant();
+        assert!(!a.needs_normalization());
+
+        // A boolean must be serialized as either 0 or 1
+        let b = Variant::from_data::<bool, _>([2u8]);
+        assert!(b.needs_normalization());
+        assert!(!b.normal_form().needs_normalization());
+    }
+
     #[test]
     fn test_print_parse() {
         let a = ("test", 1u8, 2u32).to_variant();
@@ -2477,6 +5609,27 @@ mod tests {
 
         let a3: Variant = a.to_string().parse().unwrap();
         assert_eq!(a, a3);
+
+        let a4: Variant = "[1, 2, 3]".parse().unwrap();
+        assert_eq!(a4.get::<Vec<i32>>(), Some(vec![1, 2, 3]));
+
+        let source = "[1, 'a']";
+        let err = source.parse::<Variant>().unwrap_err();
+        let context = Variant::parse_error_print_context(&err, source).unwrap();
+        assert!(context.contains(source));
+
+        let err = crate::Error::new(crate::FileError::Failed, "failed");
+        assert_eq!(Variant::parse_error_print_context(&err, source), None);
+        // A parse error without the location prefix in its message
+        let err = source.parse::<Variant>().unwrap_err();
+        let err = unsafe {
+            crate::Error::from_glib_full(ffi::g_error_new_literal(
+                err.domain().into_glib(),
+                0,
+                b"failed\0".as_ptr() as *const _,
+            ))
+        };
+        assert_eq!(Variant::parse_error_print_context(&err, source), None);
     }
 
     #[cfg(any(unix, windows))]
@@ -2489,6 +5642,41 @@ mod tests {
         assert_eq!(PathBuf::from_variant(&v), Some(path));
     }
 
+    #[test]
+    fn test_system_time() {
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+        assert_eq!(SystemTime::static_variant_type().as_str(), "x");
+
+        let t = UNIX_EPOCH + Duration::from_micros(1_234_567);
+        let v = t.to_variant();
+        assert_eq!(v.get::<i64>(), Some(1_234_567));
+        assert_eq!(v.get::<SystemTime>(), Some(t));
+
+        let t = UNIX_EPOCH - Duration::from_micros(42);
+        let v = t.to_variant();
+        assert_eq!(v.get::<i64>(), Some(-42));
+        assert_eq!(v.get::<SystemTime>(), Some(t));
+        assert_eq!(Variant::try_from(t).unwrap(), v);
+
+        // The range of `SystemTime` depends on the platform
+        if let Some(max) = UNIX_EPOCH.checked_add(Duration::from_micros(i64::MAX as u64)) {
+            assert_eq!(Variant::try_from(max).unwrap().get::<i64>(), Some(i64::MAX));
+            // Overflow past i64 microseconds
+            if let Some(t) = max.checked_add(Duration::from_micros(1)) {
+                assert!(Variant::try_from(t).is_err());
+                assert_eq!(t.to_variant().get::<i64>(), Some(i64::MAX));
+            }
+        }
+        if let Some(min) = UNIX_EPOCH.checked_sub(Duration::from_micros(i64::MIN.unsigned_abs())) {
+            assert_eq!(Variant::try_from(min).unwrap().get::<i64>(), Some(i64::MIN));
+            if let Some(t) = min.checked_sub(Duration::from_micros(1)) {
+                assert!(Variant::try_from(t).is_err());
+                assert_eq!(t.to_variant().get::<i64>(), Some(i64::MIN));
+            }
+        }
+    }
+
     #[test]
     fn test_regression_from_variant_panics() {
         let variant = "text".to_variant();
@@ -2498,5 +5686,14 @@ mod tests {
         let variant = HashMap::<u64, u64>::new().to_variant();
         let hashmap: Option<HashMap<u64, u64>> = FromVariant::from_variant(&variant);
         assert!(hashmap.is_some());
+
+        let variant = vec![(1u64, 2u64)].to_variant();
+        assert!(variant.get::<HashMap<u64, u64>>().is_none());
+        assert!(variant.get::<BTreeMap<u64, u64>>().is_none());
+        assert!(variant.try_get::<HashMap<u64, u64>>().is_err());
+
+        let variant = (1u64, 2u64).to_variant();
+        assert!(variant.get::<HashMap<u64, u64>>().is_none());
+        assert!(variant.try_get::<BTreeMap<u64, u64>>().is_err());
     }
 }
@@ -96,8 +96,26 @@ impl ExactSizeIterator for VariantIter {}
 
 impl FusedIterator for VariantIter {}
 
+// rustdoc-stripper-ignore-next
+/// Iterates over the children of a container variant.
+///
+/// # Panics
+///
+/// Panics if the variant is not a container type, like [`Variant::iter()`].
+impl IntoIterator for &Variant {
+    type Item = Variant;
+    type IntoIter = VariantIter;
+
+    fn into_iter(self) -> VariantIter {
+        self.iter()
+    }
+}
+
 // rustdoc-stripper-ignore-next
 /// Iterator over items in a variant of type `as`.
+///
+/// This is an [`ExactSizeIterator`] and [`DoubleEndedIterator`], so it can be reversed and its
+/// remaining length is known while consuming it from either end.
 #[derive(Debug)]
 pub struct VariantStrIter<'a> {
     variant: &'a Variant,
@@ -255,6 +273,22 @@ mod tests {
         assert_eq!(v.iter().count(), 2);
     }
 
+    #[test]
+    fn test_variant_into_iter() {
+        let v = (1u32, "foo").to_variant();
+        let mut children = Vec::new();
+        for child in &v {
+            children.push(child);
+        }
+        assert_eq!(children, [1u32.to_variant(), "foo".to_variant()]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_variant_into_iter_not_container() {
+        for _ in &1u32.to_variant() {}
+    }
+
     #[test]
     fn test_variant_iter_nth() {
         let v = Variant::array_from_iter::<String>([
@@ -368,4 +402,28 @@ mod tests {
         assert_eq!(iter.len(), 3);
         assert_eq!(iter.last(), Some("2"));
     }
+
+    #[test]
+    fn test_variant_str_iter_double_ended() {
+        let v = Variant::array_from_iter::<String>([
+            "0".to_string().to_variant(),
+            "1".to_string().to_variant(),
+            "2".to_string().to_variant(),
+            "3".to_string().to_variant(),
+        ]);
+
+        let rev: Vec<_> = v.array_iter_str().unwrap().rev().collect();
+        assert_eq!(rev, vec!["3", "2", "1", "0"]);
+
+        let mut iter = v.array_iter_str().unwrap();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some("0"));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next_back(), Some("3"));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.nth_back(1), Some("1"));
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
 }
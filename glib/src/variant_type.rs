@@ -35,6 +35,16 @@ impl VariantType {
     /// Tries to create a `VariantType` from a string slice.
     ///
     /// Returns `Ok` if the string is a valid type string, `Err` otherwise.
+    ///
+    /// The whole string is validated, so this is safe to use with type strings read at
+    /// runtime. [`str::parse()`] can be used as well.
+    ///
+    /// ```
+    /// let ty: glib::VariantType = "a{sv}".parse()?;
+    /// assert_eq!(ty, glib::VariantTy::VARDICT);
+    /// assert!(glib::VariantType::new("a{vs}").is_err());
+    /// # Ok::<(), glib::BoolError>(())
+    /// ```
     pub fn new(type_string: &str) -> Result<VariantType, BoolError> {
         VariantTy::new(type_string).map(ToOwned::to_owned)
     }
@@ -686,6 +696,36 @@ impl VariantTy {
 
 unsafe impl Sync for VariantTy {}
 
+impl crate::VariantClass {
+    // rustdoc-stripper-ignore-next
+    /// Returns the canonical type of the class.
+    ///
+    /// This is `Some` for the classes with exactly one type, e.g.
+    /// `VariantClass::Uint32` maps to [`VariantTy::UINT32`], and `None` for the container
+    /// classes (maybe, array, tuple and dict entry).
+    pub fn to_basic_variant_ty(self) -> Option<&'static VariantTy> {
+        use crate::VariantClass;
+
+        Some(match self {
+            VariantClass::Boolean => VariantTy::BOOLEAN,
+            VariantClass::Byte => VariantTy::BYTE,
+            VariantClass::Int16 => VariantTy::INT16,
+            VariantClass::Uint16 => VariantTy::UINT16,
+            VariantClass::Int32 => VariantTy::INT32,
+            VariantClass::Uint32 => VariantTy::UINT32,
+            VariantClass::Int64 => VariantTy::INT64,
+            VariantClass::Uint64 => VariantTy::UINT64,
+            VariantClass::Handle => VariantTy::HANDLE,
+            VariantClass::Double => VariantTy::DOUBLE,
+            VariantClass::String => VariantTy::STRING,
+            VariantClass::ObjectPath => VariantTy::OBJECT_PATH,
+            VariantClass::Signature => VariantTy::SIGNATURE,
+            VariantClass::Variant => VariantTy::VARIANT,
+            _ => return None,
+        })
+    }
+}
+
 #[doc(hidden)]
 impl<'a> ToGlibPtr<'a, *const ffi::GVariantType> for VariantTy {
     type Storage = PhantomData<&'a Self>;
@@ -1008,6 +1048,15 @@ mod tests {
         assert!(VariantTy::new("(iii)s").is_err());
     }
 
+    #[test]
+    fn new_owned() {
+        let ty = "a{sv}".parse::<VariantType>().unwrap();
+        assert_eq!(ty, VariantTy::VARDICT);
+        let err = VariantType::new("a{").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid type string: 'a{'");
+        assert!(VariantType::new("{sv}x").is_err());
+    }
+
     #[test]
     fn eq() {
         let ty1 = VariantTy::new("((iii)s)").unwrap();
@@ -1073,6 +1122,25 @@ mod tests {
         assert_eq!(VariantTy::static_type(), VariantType::static_type())
     }
 
+    #[test]
+    fn class_to_basic_variant_ty() {
+        use crate::VariantClass;
+
+        assert_eq!(
+            VariantClass::Uint32.to_basic_variant_ty(),
+            Some(VariantTy::UINT32)
+        );
+        assert_eq!(
+            VariantClass::ObjectPath.to_basic_variant_ty(),
+            Some(VariantTy::OBJECT_PATH)
+        );
+        assert_eq!(VariantClass::Array.to_basic_variant_ty(), None);
+        assert_eq!(VariantClass::Tuple.to_basic_variant_ty(), None);
+
+        let v = 42i16.to_variant();
+        assert_eq!(v.classify().to_basic_variant_ty(), Some(v.type_()));
+    }
+
     #[test]
     fn tuple_iter() {
         let ty = VariantTy::new("((iii)s)").unwrap();